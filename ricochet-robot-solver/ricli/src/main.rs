@@ -2,8 +2,9 @@ use std::collections::HashSet;
 use text_io::{read, try_scan};
 use std::env;
 
+use chrono::Duration;
 use ricochet_board::{
-    quadrant, Game, PositionEncoding, Robot, RobotPositions, Round, Symbol, Target, Position, Board, draw_board
+    quadrant, Game, PositionEncoding, Robot, RobotPositions, Round, Symbol, Target, Position, Board, draw_board, draw_path
 };
 use std::collections::{BTreeMap};
 use ricochet_solver::{IdaStar, Solver};
@@ -12,6 +13,14 @@ fn main() {
 
     let args: Vec<String> = env::args().collect();
 
+    // Anytime mode: `--time-limit <secs>` trades optimality for a bounded runtime instead of
+    // running IdaStar::solve to completion, which can take forever on large custom boards.
+    let time_limit = args
+        .iter()
+        .position(|arg| arg == "--time-limit")
+        .and_then(|i| args.get(i + 1))
+        .map(|secs| Duration::seconds(secs.parse::<i64>().expect("--time-limit expects a number of seconds")));
+
     let size_string: String = read!("{}\n");
     let size = size_string.parse::<u16>().unwrap();
 
@@ -89,7 +98,18 @@ fn main() {
         .expect("Failed to find the position of the target on the board");
     let round = Round::new(game.board().clone(), target, target_position);
 
-    let path = IdaStar::new().solve(&round, robopos);
+    let path = match time_limit {
+        Some(deadline) => match IdaStar::new().solve_within(&round, robopos.clone(), deadline) {
+            Some(anytime) => {
+                if !anytime.is_optimal() {
+                    println!("(best effort, not proven optimal)");
+                }
+                anytime.into_path()
+            }
+            None => panic!("no solution found within the time limit"),
+        },
+        None => IdaStar::new().solve(&round, robopos.clone()),
+    };
     println!("{}", path.len());
     
     if args.len() > 3 && &args[3] == "-v"{
@@ -97,5 +117,6 @@ fn main() {
         for (move_n, (robot, dir)) in movements.iter().enumerate() {
             println!(" {:>2}  {:<8}{:<6}", move_n + 1, robot, dir);
         }
+        println!("{}", draw_path(round.board(), &robopos, movements));
     }
 }
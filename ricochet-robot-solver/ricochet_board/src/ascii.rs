@@ -0,0 +1,221 @@
+//! Parses [`Game`](Game) and [`Round`](Round) instances from the same ascii grid
+//! [`Board::from_ascii`](Board::from_ascii) reads.
+//!
+//! Neither `Game` nor `Round` has anywhere to draw robots or targets today, so this introduces a
+//! small glyph convention of its own for the three content columns between each pair of walls that
+//! [`draw_board`](crate::draw_board) otherwise leaves blank (`' '`): the left column holds a
+//! target's [`Symbol`](Symbol) (`c`/`t`/`s`/`h`, blank if the field has no target or the target is
+//! [`Target::Spiral`](Target::Spiral)), the center column holds a robot's uppercase color initial
+//! (matching the mark [`draw_path`](crate::draw_path) leaves on the field it stops on), and the
+//! right column holds a target's color (`r`/`b`/`g`/`y`, or `x` for `Spiral`).
+
+use std::collections::BTreeMap;
+
+use crate::draw::{AsciiGrid, ParseError};
+use crate::{Board, Game, Position, PositionEncoding, Robot, RobotPositions, Round, Symbol, Target};
+
+impl Game {
+    /// Parses a board and its targets from `ascii`, see the [module documentation](self) for the
+    /// glyph convention used for the target columns `draw_board` otherwise leaves blank.
+    ///
+    /// Any robot glyphs present are ignored, since a `Game` doesn't track robot positions; see
+    /// [`Round::from_ascii`](Round::from_ascii) for a parser that reads those too.
+    pub fn from_ascii(ascii: &str) -> Result<Self, ParseError> {
+        let grid = AsciiGrid::parse(ascii)?;
+        let (targets, _) = scan_glyphs(&grid)?;
+        Ok(Game::new(Board::new(grid.walls()), targets))
+    }
+}
+
+impl Round {
+    /// Parses a board, its single target, and a starting [`RobotPositions`] from `ascii`, see the
+    /// [module documentation](self) for the glyph convention used for the columns `draw_board`
+    /// otherwise leaves blank.
+    ///
+    /// # Errors
+    /// Returns [`ParseError::WrongTargetCount`](ParseError::WrongTargetCount) unless the grid holds
+    /// exactly one target glyph, and [`ParseError::MissingRobot`](ParseError::MissingRobot) unless
+    /// every robot has a glyph somewhere in the grid.
+    pub fn from_ascii(ascii: &str) -> Result<(Self, RobotPositions), ParseError> {
+        let grid = AsciiGrid::parse(ascii)?;
+        let (targets, robots) = scan_glyphs(&grid)?;
+
+        let mut targets = targets.into_iter();
+        let (target, target_position) = match (targets.next(), targets.next()) {
+            (Some(only), None) => only,
+            (None, _) => return Err(ParseError::WrongTargetCount(0)),
+            (Some(_), Some(_)) => {
+                return Err(ParseError::WrongTargetCount(2 + targets.count()))
+            }
+        };
+
+        let mut positions = [Position::default(); 4];
+        for &(robot, position) in &robots {
+            positions[robot as usize] = position.ok_or(ParseError::MissingRobot(robot))?;
+        }
+
+        let round = Round::new(Board::new(grid.walls()), target, target_position);
+        let robot_positions = RobotPositions::from_tuples(&[
+            (positions[0].column(), positions[0].row()),
+            (positions[1].column(), positions[1].row()),
+            (positions[2].column(), positions[2].row()),
+            (positions[3].column(), positions[3].row()),
+        ]);
+        Ok((round, robot_positions))
+    }
+}
+
+/// Scans every field of `grid` for a target and/or robot glyph, returning the targets found and,
+/// for each robot, the position of its glyph if one was present.
+fn scan_glyphs(
+    grid: &AsciiGrid,
+) -> Result<(BTreeMap<Target, Position>, [(Robot, Option<Position>); 4]), ParseError> {
+    let side_length = grid.side_length();
+
+    let mut targets = BTreeMap::new();
+    let mut robots = [
+        (Robot::Red, None),
+        (Robot::Blue, None),
+        (Robot::Green, None),
+        (Robot::Yellow, None),
+    ];
+
+    for col in 0..side_length {
+        for row in 0..side_length {
+            let position = Position::new(col as PositionEncoding, row as PositionEncoding);
+
+            if let Some(target) = parse_target(grid.left_glyph(col, row), grid.right_glyph(col, row))? {
+                targets.insert(target, position);
+            }
+
+            if let Some(robot) = parse_robot(grid.center_glyph(col, row))? {
+                robots[robot as usize].1 = Some(position);
+            }
+        }
+    }
+
+    Ok((targets, robots))
+}
+
+/// Parses the glyph [`draw_path`](crate::draw_path) leaves on the field a robot stops on, or `None`
+/// if the field holds no robot.
+fn parse_robot(glyph: char) -> Result<Option<Robot>, ParseError> {
+    match glyph {
+        ' ' => Ok(None),
+        'R' => Ok(Some(Robot::Red)),
+        'B' => Ok(Some(Robot::Blue)),
+        'G' => Ok(Some(Robot::Green)),
+        'Y' => Ok(Some(Robot::Yellow)),
+        other => Err(ParseError::UnrecognizedGlyph(other)),
+    }
+}
+
+/// Parses a target from its `symbol`/`color` glyph pair (see the [module documentation](self)), or
+/// `None` if the field holds no target.
+///
+/// Also reused by [`BoardQuadrant::from_ascii`](crate::quadrant::BoardQuadrant::from_ascii), which
+/// shares the same symbol/color glyph convention.
+pub(crate) fn parse_target(symbol: char, color: char) -> Result<Option<Target>, ParseError> {
+    if color == ' ' {
+        return Ok(None);
+    }
+    if color == 'x' {
+        return Ok(Some(Target::Spiral));
+    }
+
+    let symbol = match symbol {
+        'c' => Symbol::Circle,
+        't' => Symbol::Triangle,
+        's' => Symbol::Square,
+        'h' => Symbol::Hexagon,
+        other => return Err(ParseError::UnrecognizedGlyph(other)),
+    };
+    match color {
+        'r' => Ok(Some(Target::Red(symbol))),
+        'b' => Ok(Some(Target::Blue(symbol))),
+        'g' => Ok(Some(Target::Green(symbol))),
+        'y' => Ok(Some(Target::Yellow(symbol))),
+        other => Err(ParseError::UnrecognizedGlyph(other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Game, Position, Robot, RobotPositions, Round, Symbol, Target};
+
+    /// A 2x2 enclosed board with a red circle target at `(1, 0)` (sharing its field with the green
+    /// robot) and robots red, blue, green, yellow at `(0, 0)`, `(0, 1)`, `(1, 0)`, `(1, 1)`.
+    fn sample_ascii() -> String {
+        let board = Board::new_empty(2).wall_enclosure();
+        let mut canvas: Vec<Vec<char>> = crate::draw_board(board.get_walls())
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+
+        // `canvas[row][col]`, following `AsciiGrid`'s own addressing.
+        let mut set = |col: usize, row: usize, offset: usize, ch: char| {
+            canvas[row * 2 + 1][col * 5 + offset] = ch;
+        };
+
+        // Field (1, 0): a red circle target, in its symbol/color columns.
+        set(1, 0, 1, 'c');
+        set(1, 0, 3, 'r');
+        // Robots, in the center column.
+        set(0, 0, 2, 'R');
+        set(0, 1, 2, 'B');
+        set(1, 0, 2, 'G');
+        set(1, 1, 2, 'Y');
+
+        canvas
+            .into_iter()
+            .map(|line| line.into_iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n"
+    }
+
+    #[test]
+    fn game_from_ascii_reads_the_target_but_ignores_robots() {
+        let game = Game::from_ascii(&sample_ascii()).expect("well-formed ascii");
+
+        assert_eq!(
+            game.get_target_position(&Target::Red(Symbol::Circle)),
+            Some(Position::new(1, 0))
+        );
+        assert_eq!(game.targets().len(), 1);
+    }
+
+    #[test]
+    fn round_from_ascii_requires_exactly_one_target() {
+        let board = Board::new_empty(2).wall_enclosure();
+        let ascii = crate::draw_board(board.get_walls());
+
+        assert_eq!(
+            Round::from_ascii(&ascii).unwrap_err(),
+            super::ParseError::WrongTargetCount(0)
+        );
+    }
+
+    #[test]
+    fn round_from_ascii_requires_every_robot() {
+        let mut ascii = sample_ascii();
+        ascii = ascii.replacen('R', " ", 1);
+
+        assert_eq!(
+            Round::from_ascii(&ascii).unwrap_err(),
+            super::ParseError::MissingRobot(Robot::Red)
+        );
+    }
+
+    #[test]
+    fn round_from_ascii_reads_the_target_and_every_robot() {
+        let (round, positions) = Round::from_ascii(&sample_ascii()).expect("well-formed ascii");
+
+        assert_eq!(round.target(), Target::Red(Symbol::Circle));
+        assert_eq!(round.target_position(), Position::new(1, 0));
+        assert_eq!(
+            positions,
+            RobotPositions::from_tuples(&[(0, 0), (0, 1), (1, 0), (1, 1)])
+        );
+    }
+}
@@ -1,19 +1,241 @@
-use crate::Field;
 use draw_a_box::{find_character, Weight};
 
+use crate::{Board, Direction, Field, Grid, Robot, RobotPositions, Round, Symbol, Target, Walls, ROBOTS};
+
 /// Width per field in the string in number of characters.
 pub const FIELD_DRAW_WIDTH: usize = 5;
 
 /// Height per field in the string in number of characters.
 pub const FIELD_DRAW_HEIGHT: usize = 2;
 
+/// Error returned by [`Board::from_ascii`](Board::from_ascii) and the `Game`/`Round` ascii parsers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input was empty.
+    Empty,
+    /// Not every line had the same number of characters.
+    RaggedLines,
+    /// The grid isn't a whole number of square fields arranged into a square board.
+    InvalidDimensions,
+    /// A field held a glyph that isn't one of the recognized robot or target characters.
+    UnrecognizedGlyph(char),
+    /// [`Round::from_ascii`](crate::Round::from_ascii) requires the grid to hold exactly one
+    /// target glyph, but it found this many.
+    WrongTargetCount(usize),
+    /// [`Round::from_ascii`](crate::Round::from_ascii) requires a glyph for every robot, but this
+    /// one was missing.
+    MissingRobot(Robot),
+}
+
+impl Board {
+    /// Parses a board back from the string produced by [`draw_board`](draw_board).
+    ///
+    /// Infers the side length from the grid and reconstructs each [`Field`](Field)'s `down`/`right`
+    /// flags from the shared wall segment drawn between adjacent cells, so
+    /// `Board::from_ascii(&draw_board(board.get_walls()))` round-trips `board` for any `board`.
+    pub fn from_ascii(ascii: &str) -> Result<Self, ParseError> {
+        let grid = AsciiGrid::parse(ascii)?;
+        Ok(Board::new(grid.walls()))
+    }
+}
+
 /// Creates a string representation of the walls of a board.
-pub fn draw_board(walls: &[Vec<Field>]) -> String {
+pub fn draw_board(walls: &Grid<Field>) -> String {
     let (canvas, _) = create_board_string_vec(walls);
+    canvas_to_string(&canvas)
+}
+
+/// Draws `board`'s walls with the trajectory of `movements`, played out from `start`, overlaid on
+/// top.
+///
+/// Every field a robot slides across while making one of the `movements` is marked with its
+/// lowercase color initial; the field it comes to rest on once that move is finished is marked with
+/// the uppercase initial instead, so a stop stands out from a fly-by. If multiple robots (or
+/// multiple moves of the same robot) cross the same field, the mark from the latest move in
+/// `movements` wins.
+pub fn draw_path(board: &Board, start: &RobotPositions, movements: &[(Robot, Direction)]) -> String {
+    let side_length = board.side_length() as usize;
+    let mut marks: Vec<Vec<Option<(Robot, bool)>>> = vec![vec![None; side_length]; side_length];
+
+    let mut positions = start.clone();
+    for &(robot, direction) in movements {
+        // Mirrors `RobotPositions::move_in_direction`'s walk, but records every intermediate
+        // `Position` instead of only the final one.
+        let mut pos = positions[robot];
+        while !board.is_adjacent_to_wall(pos, direction)
+            && !positions.contains_any_robot(pos.to_direction(direction, board.side_length()))
+        {
+            pos = pos.to_direction(direction, board.side_length());
+            marks[pos.column() as usize][pos.row() as usize] = Some((robot, false));
+        }
+
+        positions = positions.move_in_direction(board, robot, direction);
+        let stop = positions[robot];
+        marks[stop.column() as usize][stop.row() as usize] = Some((robot, true));
+    }
+
+    let (mut canvas, _) = create_board_string_vec(board.get_walls());
+    for (col, column) in marks.iter().enumerate() {
+        for (row, mark) in column.iter().enumerate() {
+            if let Some((robot, stopped)) = mark {
+                let canvas_col = col * FIELD_DRAW_WIDTH + FIELD_DRAW_WIDTH / 2;
+                let canvas_row = row * FIELD_DRAW_HEIGHT + 1;
+                canvas[canvas_col][canvas_row] = robot_mark(*robot, *stopped);
+            }
+        }
+    }
+
+    canvas_to_string(&canvas)
+}
+
+/// Returns the single-character mark used to draw `robot` in [`draw_path`](draw_path), lowercase
+/// while sliding through a field and uppercase on the field it stops on.
+fn robot_mark(robot: Robot, stopped: bool) -> &'static str {
+    match (robot, stopped) {
+        (Robot::Red, false) => "r",
+        (Robot::Red, true) => "R",
+        (Robot::Blue, false) => "b",
+        (Robot::Blue, true) => "B",
+        (Robot::Green, false) => "g",
+        (Robot::Green, true) => "G",
+        (Robot::Yellow, false) => "y",
+        (Robot::Yellow, true) => "Y",
+    }
+}
+
+/// Renders `round`'s board with `positions`' robots and the round's target overlaid.
+///
+/// Reuses the three-column glyph convention the [`ascii`](crate::ascii) module parses back: the
+/// target's [`Symbol`] in the left content column, its color in the right, and every robot as an
+/// uppercase color initial (see [`robot_mark`](robot_mark)) in the center. Behind the `ansi-color`
+/// feature, every glyph is additionally wrapped in the ANSI escape for its `Robot`/`Target` color,
+/// for interactive terminal front-ends; without it, `draw_round` renders identically to the plain
+/// text `ascii` round-trips.
+pub fn draw_round(round: &Round, positions: &RobotPositions) -> String {
+    let (canvas, _) = create_board_string_vec(round.board().get_walls());
+    let mut canvas: Vec<Vec<String>> = canvas
+        .into_iter()
+        .map(|column| column.into_iter().map(str::to_string).collect())
+        .collect();
+
+    let target = round.target();
+    let target_pos = round.target_position();
+    let target_base_col = target_pos.column() as usize * FIELD_DRAW_WIDTH;
+    let target_row = target_pos.row() as usize * FIELD_DRAW_HEIGHT + 1;
+    let target_color = target_color(target);
+    canvas[target_base_col + 1][target_row] = colorize(target_symbol_glyph(target), target_color);
+    canvas[target_base_col + 3][target_row] = colorize(target_color_glyph(target), target_color);
+
+    for &robot in &ROBOTS {
+        let pos = positions[robot];
+        let col = pos.column() as usize * FIELD_DRAW_WIDTH + FIELD_DRAW_WIDTH / 2;
+        let row = pos.row() as usize * FIELD_DRAW_HEIGHT + 1;
+        canvas[col][row] = colorize(robot_mark(robot, true), Some(robot_color(robot)));
+    }
+
+    let mut output = String::new();
+    for row in 0..canvas[0].len() {
+        for column in &canvas {
+            output.push_str(&column[row]);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// A color a [`Robot`] or colored [`Target`] can be rendered in by [`draw_round`](draw_round).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Color {
+    Red,
+    Blue,
+    Green,
+    Yellow,
+}
+
+/// The color `draw_round` renders `robot` in.
+pub(crate) fn robot_color(robot: Robot) -> Color {
+    match robot {
+        Robot::Red => Color::Red,
+        Robot::Blue => Color::Blue,
+        Robot::Green => Color::Green,
+        Robot::Yellow => Color::Yellow,
+    }
+}
+
+/// The color `draw_round` renders `target` in, or `None` for [`Target::Spiral`](Target::Spiral),
+/// which any robot can reach.
+pub(crate) fn target_color(target: Target) -> Option<Color> {
+    match target {
+        Target::Red(_) => Some(Color::Red),
+        Target::Blue(_) => Some(Color::Blue),
+        Target::Green(_) => Some(Color::Green),
+        Target::Yellow(_) => Some(Color::Yellow),
+        Target::Spiral => None,
+    }
+}
+
+/// The glyph drawn for `target`'s [`Symbol`], blank for [`Target::Spiral`](Target::Spiral) which
+/// has none, matching the convention [`ascii`](crate::ascii) parses back.
+pub(crate) fn target_symbol_glyph(target: Target) -> &'static str {
+    match target {
+        Target::Spiral => " ",
+        Target::Red(symbol)
+        | Target::Blue(symbol)
+        | Target::Green(symbol)
+        | Target::Yellow(symbol) => match symbol {
+            Symbol::Circle => "c",
+            Symbol::Triangle => "t",
+            Symbol::Square => "s",
+            Symbol::Hexagon => "h",
+        },
+    }
+}
+
+/// The glyph drawn for `target`'s color, matching the convention [`ascii`](crate::ascii) parses
+/// back.
+pub(crate) fn target_color_glyph(target: Target) -> &'static str {
+    match target {
+        Target::Red(_) => "r",
+        Target::Blue(_) => "b",
+        Target::Green(_) => "g",
+        Target::Yellow(_) => "y",
+        Target::Spiral => "x",
+    }
+}
+
+/// Wraps `glyph` in the ANSI escape for `color`, if the `ansi-color` feature is enabled.
+#[cfg(feature = "ansi-color")]
+fn colorize(glyph: &str, color: Option<Color>) -> String {
+    match color {
+        Some(color) => format!("\u{1b}[{}m{}\u{1b}[0m", ansi_code(color), glyph),
+        None => glyph.to_string(),
+    }
+}
+
+/// Without the `ansi-color` feature, `draw_round` renders plain, uncolored glyphs.
+#[cfg(not(feature = "ansi-color"))]
+fn colorize(glyph: &str, _color: Option<Color>) -> String {
+    glyph.to_string()
+}
+
+/// The ANSI foreground color code for `color`.
+#[cfg(feature = "ansi-color")]
+fn ansi_code(color: Color) -> u8 {
+    match color {
+        Color::Red => 31,
+        Color::Blue => 34,
+        Color::Green => 32,
+        Color::Yellow => 33,
+    }
+}
+
+/// Flattens a canvas created by [`create_board_string_vec`](create_board_string_vec) into a single
+/// string, one line per row.
+fn canvas_to_string(canvas: &[Vec<&str>]) -> String {
     let mut output = String::new();
 
     for row in 0..canvas[0].len() {
-        for col in &canvas {
+        for col in canvas {
             output.push_str(col[row]);
         }
         output.push('\n');
@@ -28,9 +250,9 @@ pub fn draw_board(walls: &[Vec<Field>]) -> String {
 /// The second returned value has the same size but each element is a vec containing the four
 /// weights describing the string at the same position in the first value. The second value actually
 /// only contains information regarding corners.
-pub fn create_board_string_vec(walls: &[Vec<Field>]) -> (Vec<Vec<&str>>, Vec<Vec<Vec<Weight>>>) {
-    let width = walls.len();
-    let height = walls[0].len();
+pub fn create_board_string_vec(walls: &Grid<Field>) -> (Vec<Vec<&str>>, Vec<Vec<Vec<Weight>>>) {
+    let width = walls.width();
+    let height = walls.height();
     let canvas_width = width * FIELD_DRAW_WIDTH + 1;
     let canvas_height = height * FIELD_DRAW_HEIGHT + 1;
 
@@ -107,3 +329,173 @@ pub fn create_board_string_vec(walls: &[Vec<Field>]) -> (Vec<Vec<&str>>, Vec<Vec
 
     (canvas, corner_weights)
 }
+
+/// A parsed ascii grid: the raw character lines plus the inferred side length.
+///
+/// Shared by [`Board::from_ascii`](Board::from_ascii) and the `Game`/`Round` parsers in the
+/// [`ascii`](crate::ascii) module, which additionally read the robot and target glyphs drawn in the
+/// three content columns between each pair of vertical wall segments.
+///
+/// Unrelated to the generic [`Grid`](crate::Grid) backing [`Board`](Board)'s walls: this one is a
+/// `Vec` of raw character rows, not a bounds-checked store of game data.
+pub(crate) struct AsciiGrid {
+    lines: Vec<Vec<char>>,
+    side_length: usize,
+}
+
+impl AsciiGrid {
+    pub(crate) fn parse(ascii: &str) -> Result<Self, ParseError> {
+        let lines: Vec<Vec<char>> = ascii.lines().map(|line| line.chars().collect()).collect();
+        if lines.is_empty() || lines[0].is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let canvas_width = lines[0].len();
+        if lines.iter().any(|line| line.len() != canvas_width) {
+            return Err(ParseError::RaggedLines);
+        }
+        let canvas_height = lines.len();
+
+        if (canvas_width - 1) % FIELD_DRAW_WIDTH != 0 || (canvas_height - 1) % FIELD_DRAW_HEIGHT != 0 {
+            return Err(ParseError::InvalidDimensions);
+        }
+        let side_length = (canvas_width - 1) / FIELD_DRAW_WIDTH;
+        if side_length == 0 || side_length != (canvas_height - 1) / FIELD_DRAW_HEIGHT {
+            return Err(ParseError::InvalidDimensions);
+        }
+
+        Ok(AsciiGrid { lines, side_length })
+    }
+
+    pub(crate) fn side_length(&self) -> usize {
+        self.side_length
+    }
+
+    /// Reconstructs `walls` from the wall segment shared between every pair of adjacent cells.
+    pub(crate) fn walls(&self) -> Walls {
+        let heavy_vertical = single_char(find_character(
+            Weight::Heavy,
+            Weight::Empty,
+            Weight::Heavy,
+            Weight::Empty,
+        ));
+        let heavy_horizontal = single_char(find_character(
+            Weight::Empty,
+            Weight::Heavy,
+            Weight::Empty,
+            Weight::Heavy,
+        ));
+
+        let mut walls = vec![vec![Field::default(); self.side_length]; self.side_length];
+        for col in 0..self.side_length {
+            for row in 0..self.side_length {
+                walls[col][row].right = self.lines[row * FIELD_DRAW_HEIGHT + 1]
+                    [(col + 1) * FIELD_DRAW_WIDTH]
+                    == heavy_vertical;
+                walls[col][row].down = self.lines[(row + 1) * FIELD_DRAW_HEIGHT]
+                    [col * FIELD_DRAW_WIDTH + 1]
+                    == heavy_horizontal;
+            }
+        }
+        walls
+    }
+
+    /// The glyph drawn at the center of field `(col, row)`, used by [`draw_path`](draw_path) to mark
+    /// robots.
+    pub(crate) fn center_glyph(&self, col: usize, row: usize) -> char {
+        self.lines[row * FIELD_DRAW_HEIGHT + 1][col * FIELD_DRAW_WIDTH + FIELD_DRAW_WIDTH / 2]
+    }
+
+    /// The glyph one content column left of the center of field `(col, row)`, used to mark a
+    /// target's [`Symbol`](crate::Symbol).
+    pub(crate) fn left_glyph(&self, col: usize, row: usize) -> char {
+        self.lines[row * FIELD_DRAW_HEIGHT + 1][col * FIELD_DRAW_WIDTH + 1]
+    }
+
+    /// The glyph one content column right of the center of field `(col, row)`, used to mark a
+    /// target's color.
+    pub(crate) fn right_glyph(&self, col: usize, row: usize) -> char {
+        self.lines[row * FIELD_DRAW_HEIGHT + 1][col * FIELD_DRAW_WIDTH + 3]
+    }
+}
+
+fn single_char(s: &str) -> char {
+    s.chars()
+        .next()
+        .expect("draw_a_box glyphs are always a single character")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::draw_path;
+    use crate::{quadrant, Board, Direction, Robot, RobotPositions, Round, Symbol, Target};
+
+    #[test]
+    fn draw_path_marks_crossed_and_stopped_fields() {
+        let board = Board::new_empty(3).wall_enclosure();
+        let start = RobotPositions::from_tuples(&[(0, 0), (1, 2), (2, 2), (0, 1)]);
+
+        let output = draw_path(&board, &start, &[(Robot::Red, Direction::Right)]);
+
+        assert!(output.contains('r'), "crossed field not marked:\n{}", output);
+        assert!(output.contains('R'), "stopped field not marked:\n{}", output);
+    }
+
+    #[test]
+    fn draw_round_marks_the_target_and_every_robot() {
+        let board = Board::new_empty(3).wall_enclosure();
+        let round = Round::new(board, Target::Green(Symbol::Hexagon), crate::Position::new(2, 2));
+        let positions = RobotPositions::from_tuples(&[(0, 0), (1, 0), (2, 0), (0, 1)]);
+
+        let output = super::draw_round(&round, &positions);
+
+        assert!(output.contains('h'), "target symbol not marked:\n{}", output);
+        assert!(output.contains('g'), "target color not marked:\n{}", output);
+        assert!(output.contains('R'), "red robot not marked:\n{}", output);
+        assert!(output.contains('B'), "blue robot not marked:\n{}", output);
+        assert!(output.contains('G'), "green robot not marked:\n{}", output);
+        assert!(output.contains('Y'), "yellow robot not marked:\n{}", output);
+    }
+
+    #[test]
+    fn from_ascii_round_trips_through_draw_board_for_an_empty_board() {
+        let board = Board::new_empty(5).wall_enclosure();
+        let ascii = super::draw_board(board.get_walls());
+
+        assert_eq!(Board::from_ascii(&ascii), Ok(board));
+    }
+
+    #[test]
+    fn from_ascii_round_trips_through_draw_board_for_a_full_board() {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+        let board = crate::Game::from_quadrants(&quadrants).board().clone();
+        let ascii = super::draw_board(board.get_walls());
+
+        assert_eq!(Board::from_ascii(&ascii), Ok(board));
+    }
+
+    #[test]
+    fn from_ascii_rejects_ragged_input() {
+        assert_eq!(
+            Board::from_ascii("abc\nab"),
+            Err(super::ParseError::RaggedLines)
+        );
+    }
+
+    #[test]
+    fn from_ascii_rejects_dimensions_that_arent_a_whole_number_of_fields() {
+        assert_eq!(
+            Board::from_ascii("abc\ndef\nghi"),
+            Err(super::ParseError::InvalidDimensions)
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! A compact, canonical string encoding for an arbitrary [`Round`](Round) and its starting
+//! [`RobotPositions`](RobotPositions) — a "Ricochet FEN" for sharing or reloading custom or
+//! procedurally generated puzzles, independent of the fixed 16x16 standard board and the
+//! [`round_from_seed`](crate::quadrant::round_from_seed)/[`game_from_seed`](crate::quadrant::game_from_seed)
+//! integer seeds, which can only express the 8262 standard rounds.
+//!
+//! Cells are addressed with the same flat index chess notations use for their own squares:
+//! `index = column * side_length + row`. The encoding has five `;`-separated fields:
+//! 1. the board's side length;
+//! 2. a comma-separated list of `index:bits` wall entries, one per cell with at least one wall,
+//!    where bit 0 marks a wall below the cell and bit 1 a wall to its right;
+//! 3. a comma-separated list of `index:color:symbol` target entries, using the same glyph
+//!    convention [`ascii`](crate::ascii) parses (`symbol` is blank for
+//!    [`Target::Spiral`](Target::Spiral));
+//! 4. a comma-separated list of the four robots' cell indices, in red/blue/green/yellow order;
+//! 5. the active target's `color:symbol`, matching one of the entries from field 3.
+
+use crate::ascii::parse_target;
+use crate::draw::{target_color_glyph, target_symbol_glyph};
+use crate::{Board, Field, Position, PositionEncoding, RobotPositions, Round, Target, ROBOTS};
+
+/// Error returned by [`Round::from_encoding`](Round::from_encoding).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    /// The encoding didn't split into the five `;`-separated fields described in the
+    /// [module documentation](self).
+    WrongFieldCount(usize),
+    /// The side length field wasn't a valid, non-zero integer.
+    InvalidDimension(String),
+    /// A wall or target entry's cell index wasn't a valid integer within the board.
+    InvalidCellIndex(String),
+    /// A wall entry's bits weren't one of `1..=3` (an entry is only written for cells with a wall).
+    InvalidWallBits(String),
+    /// A target entry, or the active target field, didn't hold a recognized color/symbol pair.
+    InvalidTarget(String),
+    /// The robot position field didn't list exactly four cell indices.
+    WrongRobotCount(usize),
+    /// The active target field didn't match any entry from the target list.
+    UnknownActiveTarget(String),
+}
+
+/// The flat `column * side_length + row` index [`Round::to_encoding`] addresses cells with.
+fn cell_index(pos: Position, side_length: PositionEncoding) -> usize {
+    pos.column() as usize * side_length as usize + pos.row() as usize
+}
+
+/// The inverse of [`cell_index`], or `None` if `index` falls outside the board.
+fn position_from_index(index: usize, side_length: PositionEncoding) -> Option<Position> {
+    let side_length = side_length as usize;
+    if side_length == 0 || index >= side_length * side_length {
+        return None;
+    }
+    Some(Position::new(
+        (index / side_length) as PositionEncoding,
+        (index % side_length) as PositionEncoding,
+    ))
+}
+
+/// Parses a `color:symbol` (or `color`-only, for the active target field) glyph pair back into a
+/// [`Target`], reusing [`ascii`](crate::ascii)'s glyph convention.
+fn parse_target_glyphs(color: char, symbol: char) -> Option<Target> {
+    parse_target(symbol, color).ok().flatten()
+}
+
+impl Round {
+    /// Encodes this round and `positions` into the compact string format described in the
+    /// [module documentation](self).
+    pub fn to_encoding(&self, positions: &RobotPositions) -> String {
+        let side_length = self.board.side_length();
+
+        let walls: Vec<String> = (0..side_length)
+            .flat_map(|col| (0..side_length).map(move |row| Position::new(col, row)))
+            .filter_map(|pos| {
+                let field = self.board[pos];
+                let bits = field.down as u8 | ((field.right as u8) << 1);
+                (bits != 0).then(|| format!("{}:{}", cell_index(pos, side_length), bits))
+            })
+            .collect();
+
+        let target_entry = format!(
+            "{}:{}:{}",
+            cell_index(self.target_position, side_length),
+            target_color_glyph(self.target),
+            target_symbol_glyph(self.target)
+        );
+
+        let robots = ROBOTS
+            .iter()
+            .map(|&robot| cell_index(positions[robot], side_length).to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!(
+            "{};{};{};{};{}:{}",
+            side_length,
+            walls.join(","),
+            target_entry,
+            robots,
+            target_color_glyph(self.target),
+            target_symbol_glyph(self.target)
+        )
+    }
+
+    /// Decodes a round and its starting robot positions from `encoding`, the inverse of
+    /// [`to_encoding`](Round::to_encoding).
+    pub fn from_encoding(encoding: &str) -> Result<(Self, RobotPositions), EncodingError> {
+        let fields: Vec<&str> = encoding.split(';').collect();
+        if fields.len() != 5 {
+            return Err(EncodingError::WrongFieldCount(fields.len()));
+        }
+        let (dimension, walls_field, targets_field, robots_field, active_field) =
+            (fields[0], fields[1], fields[2], fields[3], fields[4]);
+
+        let side_length: PositionEncoding = dimension
+            .parse()
+            .ok()
+            .filter(|&len: &PositionEncoding| len > 0)
+            .ok_or_else(|| EncodingError::InvalidDimension(dimension.to_string()))?;
+
+        let mut board = Board::new_empty(side_length);
+        if !walls_field.is_empty() {
+            for entry in walls_field.split(',') {
+                let (index, bits) = entry
+                    .split_once(':')
+                    .ok_or_else(|| EncodingError::InvalidCellIndex(entry.to_string()))?;
+                let pos = index
+                    .parse()
+                    .ok()
+                    .and_then(|index| position_from_index(index, side_length))
+                    .ok_or_else(|| EncodingError::InvalidCellIndex(entry.to_string()))?;
+                let bits: u8 = bits
+                    .parse()
+                    .ok()
+                    .filter(|&bits| (1..=3).contains(&bits))
+                    .ok_or_else(|| EncodingError::InvalidWallBits(entry.to_string()))?;
+                board[pos] = Field {
+                    down: bits & 1 != 0,
+                    right: bits & 2 != 0,
+                };
+            }
+        }
+
+        let mut targets: Vec<(Position, Target)> = Vec::new();
+        if !targets_field.is_empty() {
+            for entry in targets_field.split(',') {
+                let mut parts = entry.splitn(3, ':');
+                let (index, color, symbol) = match (parts.next(), parts.next(), parts.next()) {
+                    (Some(index), Some(color), Some(symbol)) => (index, color, symbol),
+                    _ => return Err(EncodingError::InvalidTarget(entry.to_string())),
+                };
+                let pos = index
+                    .parse()
+                    .ok()
+                    .and_then(|index| position_from_index(index, side_length))
+                    .ok_or_else(|| EncodingError::InvalidCellIndex(entry.to_string()))?;
+                let color = color
+                    .chars()
+                    .next()
+                    .ok_or_else(|| EncodingError::InvalidTarget(entry.to_string()))?;
+                let symbol = symbol.chars().next().unwrap_or(' ');
+                let target = parse_target_glyphs(color, symbol)
+                    .ok_or_else(|| EncodingError::InvalidTarget(entry.to_string()))?;
+                targets.push((pos, target));
+            }
+        }
+
+        let robot_indices: Vec<&str> = robots_field.split(',').collect();
+        if robot_indices.len() != 4 {
+            return Err(EncodingError::WrongRobotCount(robot_indices.len()));
+        }
+        let mut robot_positions: [(PositionEncoding, PositionEncoding); 4] = [(0, 0); 4];
+        for (slot, index) in robot_positions.iter_mut().zip(robot_indices.iter()) {
+            let pos = index
+                .parse()
+                .ok()
+                .and_then(|index| position_from_index(index, side_length))
+                .ok_or_else(|| EncodingError::InvalidCellIndex(index.to_string()))?;
+            *slot = (pos.column(), pos.row());
+        }
+        let positions = RobotPositions::from_tuples(&robot_positions);
+
+        let mut active = active_field.splitn(2, ':');
+        let (color, symbol) = match (active.next(), active.next()) {
+            (Some(color), Some(symbol)) => (color, symbol),
+            _ => return Err(EncodingError::InvalidTarget(active_field.to_string())),
+        };
+        let color = color
+            .chars()
+            .next()
+            .ok_or_else(|| EncodingError::InvalidTarget(active_field.to_string()))?;
+        let symbol = symbol.chars().next().unwrap_or(' ');
+        let active_target = parse_target_glyphs(color, symbol)
+            .ok_or_else(|| EncodingError::InvalidTarget(active_field.to_string()))?;
+
+        let target_position = targets
+            .iter()
+            .find(|&&(_, target)| target == active_target)
+            .map(|&(pos, _)| pos)
+            .ok_or_else(|| EncodingError::UnknownActiveTarget(active_field.to_string()))?;
+
+        Ok((
+            Round::new(board, active_target, target_position),
+            positions,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EncodingError;
+    use crate::{quadrant, Board, RobotPositions, Round, Symbol, Target};
+
+    fn sample_round() -> (Round, RobotPositions) {
+        let game = quadrant::game_from_seed(3);
+        let target = *game.targets().keys().next().expect("game has targets");
+        let target_position = game.get_target_position(&target).unwrap();
+        let round = Round::new(game.board().clone(), target, target_position);
+        let positions = RobotPositions::from_tuples(&[(0, 0), (1, 1), (2, 2), (3, 3)]);
+        (round, positions)
+    }
+
+    #[test]
+    fn round_trips_through_its_own_encoding() {
+        let (round, positions) = sample_round();
+        let encoding = round.to_encoding(&positions);
+        let (decoded_round, decoded_positions) =
+            Round::from_encoding(&encoding).expect("well-formed encoding");
+
+        assert_eq!(decoded_round.board(), round.board());
+        assert_eq!(decoded_round.target(), round.target());
+        assert_eq!(decoded_round.target_position(), round.target_position());
+        assert_eq!(decoded_positions, positions);
+    }
+
+    #[test]
+    fn round_trips_a_custom_non_standard_board() {
+        let board = Board::new_empty(4)
+            .wall_enclosure()
+            .set_vertical_line(1, 1, 1)
+            .set_horizontal_line(0, 2, 2);
+        let round = Round::new(board, Target::Red(Symbol::Hexagon), crate::Position::new(1, 1));
+        let positions = RobotPositions::from_tuples(&[(0, 0), (3, 0), (0, 3), (3, 3)]);
+
+        let encoding = round.to_encoding(&positions);
+        let (decoded_round, decoded_positions) =
+            Round::from_encoding(&encoding).expect("well-formed encoding");
+
+        assert_eq!(decoded_round.board(), round.board());
+        assert_eq!(decoded_round.target(), round.target());
+        assert_eq!(decoded_round.target_position(), round.target_position());
+        assert_eq!(decoded_positions, positions);
+    }
+
+    #[test]
+    fn from_encoding_rejects_the_wrong_field_count() {
+        assert_eq!(
+            Round::from_encoding("4;;;0,0,0,0"),
+            Err(EncodingError::WrongFieldCount(4))
+        );
+    }
+
+    #[test]
+    fn from_encoding_rejects_a_zero_dimension() {
+        assert_eq!(
+            Round::from_encoding("0;;;0,0,0,0;r:c"),
+            Err(EncodingError::InvalidDimension("0".to_string()))
+        );
+    }
+
+    #[test]
+    fn from_encoding_rejects_a_robot_field_without_four_entries() {
+        assert_eq!(
+            Round::from_encoding("4;;0:r:c;0,1,2;r:c"),
+            Err(EncodingError::WrongRobotCount(3))
+        );
+    }
+
+    #[test]
+    fn from_encoding_rejects_an_active_target_absent_from_the_target_list() {
+        assert_eq!(
+            Round::from_encoding("4;;0:r:c;0,1,2,3;b:t"),
+            Err(EncodingError::UnknownActiveTarget("b:t".to_string()))
+        );
+    }
+
+    #[test]
+    fn spiral_targets_round_trip_without_a_symbol_glyph() {
+        let board = Board::new_empty(2).wall_enclosure();
+        let round = Round::new(board, Target::Spiral, crate::Position::new(0, 0));
+        let positions = RobotPositions::from_tuples(&[(0, 0), (0, 1), (1, 0), (1, 1)]);
+
+        let encoding = round.to_encoding(&positions);
+        let (decoded_round, _) = Round::from_encoding(&encoding).expect("well-formed encoding");
+        assert_eq!(decoded_round.target(), Target::Spiral);
+    }
+}
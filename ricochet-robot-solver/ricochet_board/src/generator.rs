@@ -1,15 +1,613 @@
 //! Tools to generate boards of different sizes.
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::{fmt, mem};
 
-use crate::{Board, Direction, Game, Position, PositionEncoding};
+use crate::{Board, Direction, Game, Position, PositionEncoding, RobotPositions, Round};
 use itertools::Itertools;
 use rand::prelude::SliceRandom;
-use rand::{Rng, SeedableRng};
+use rand::{Rng, RngCore, SeedableRng};
 
 /// Marks the side_length from which on generated boards contain a center wall block.
 pub const CENTER_WALLS_FROM_SIDE_LENGTH: PositionEncoding = 10;
 
+/// Default minimum sub-rectangle side length [`Generator::generate_board_bsp`] refuses to split
+/// below.
+pub const DEFAULT_BSP_MIN_CELL: PositionEncoding = 4;
+
+/// Tunable parameters controlling how sparse or dense a generated board's walls and targets are.
+///
+/// Passed to every [`BoardFilter`] in [`Generator::generate_board`]'s pipeline (and consulted
+/// directly by [`Generator::generate_random_walk_board`] and
+/// [`Generator::generate_board_bsp`]), replacing what used to be constants fixed at compile time.
+/// [`Default`] reproduces the generator's original, pre-`GenerationConfig` behavior.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationConfig {
+    /// The fraction of a board's side length used as the number of corner walls (and potential
+    /// targets) [`quadrant_corner_walls`] scatters into each quadrant.
+    pub target_density: f64,
+    /// The fraction of a board's side length used as the number of wall protrusions
+    /// [`outer_wall_protrusions`] juts in from each outer wall.
+    pub protrusion_density: f64,
+    /// The minimum side length at which [`CenterWalls`] adds a center wall block.
+    pub center_walls_threshold: PositionEncoding,
+    /// Relative weights, in [`DIRECTIONS`](crate::DIRECTIONS) order, for which way
+    /// [`place_corner_wall`] opens a scattered corner wall.
+    pub corner_wall_weights: [f64; 4],
+    /// The probability [`corridor_walls`]' walker repeats its previous step's direction instead of
+    /// sampling a fresh one from [`corner_wall_weights`](Self::corner_wall_weights). Low by
+    /// default, so corridors stay short and twisty rather than running in long straight lines.
+    pub momentum_prob: f64,
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        Self {
+            // Reproduces the `(side_length as f64 / 4.0).round()` this crate used before
+            // `GenerationConfig` existed.
+            target_density: 0.25,
+            // Reproduces the `(side_length + 7) / 8` integer formula this crate used before
+            // `GenerationConfig` existed.
+            protrusion_density: 0.125,
+            center_walls_threshold: CENTER_WALLS_FROM_SIDE_LENGTH,
+            corner_wall_weights: [1.0, 1.0, 1.0, 1.0],
+            momentum_prob: 0.1,
+        }
+    }
+}
+
+/// Selects which strategy [`Generator`] uses to distribute a board's corner walls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenerationStrategy {
+    /// Scatters corner walls across four fixed quadrants; see
+    /// [`Generator::generate_board`]. Clusters unevenly on large boards, since every quadrant gets
+    /// the same number of walls regardless of how they end up spaced out within it.
+    Quadrant,
+    /// Distributes corner walls with recursive binary space partitioning, for placement that stays
+    /// spatially even independent of board size; see [`Generator::generate_board_bsp`] with
+    /// [`DEFAULT_BSP_MIN_CELL`].
+    Bsp,
+    /// Grows short, connected wall segments via a momentum-biased random walk instead of scattering
+    /// isolated corner walls, for more maze-like boards with longer forced slides; see
+    /// [`Generator::generate_board_corridor`].
+    Corridor,
+}
+
+/// A single step of [`Generator::generate_board`]'s pipeline.
+///
+/// A filter mutates `board`'s walls in place and records, in `occupied` and `potential_targets`,
+/// which fields it has claimed so that later filters in the pipeline don't place something else on
+/// top. Implement this to insert a custom step (e.g. an extra symmetry pass) into
+/// [`Generator::filters_mut`] without forking `generate_board` itself.
+///
+/// Takes `rng` as `&mut dyn RngCore` rather than `&mut impl Rng` so that `Box<dyn BoardFilter>` is
+/// object-safe; [`Rng`](rand::Rng)'s methods are available on it regardless, via `rand`'s blanket
+/// impl for any `RngCore`.
+///
+/// A custom pipeline must still end with something equivalent to [`WallEnclosure`]:
+/// [`generate_board`](Generator::generate_board)'s post-pass floods the board outwards from its
+/// corners to find unreachable targets, which never terminates without an outer wall to stop the
+/// flood.
+pub trait BoardFilter: fmt::Debug {
+    /// Applies this filter to `board` in place.
+    fn apply(
+        &self,
+        rng: &mut dyn RngCore,
+        config: &GenerationConfig,
+        board: &mut Board,
+        occupied: &mut BTreeSet<Position>,
+        potential_targets: &mut Vec<Position>,
+    );
+}
+
+/// Sets the walls around the enclosed 2x2 block in the center of boards at least
+/// [`GenerationConfig::center_walls_threshold`] wide ([`CENTER_WALLS_FROM_SIDE_LENGTH`] by
+/// default). A no-op on smaller boards.
+#[derive(Debug, Clone, Copy)]
+pub struct CenterWalls;
+
+impl BoardFilter for CenterWalls {
+    fn apply(
+        &self,
+        _rng: &mut dyn RngCore,
+        config: &GenerationConfig,
+        board: &mut Board,
+        occupied: &mut BTreeSet<Position>,
+        _potential_targets: &mut Vec<Position>,
+    ) {
+        let side_length = board.side_length();
+        if side_length < config.center_walls_threshold {
+            return;
+        }
+
+        *board = mem::take(board).set_center_walls();
+        let f = side_length / 2 - 1;
+        for (col_add, row_add) in [0, 1].iter().cartesian_product(&[0, 1]) {
+            dilate_occupied(occupied, side_length, Position::new(f + col_add, f + row_add));
+        }
+    }
+}
+
+/// Adds short wall protrusions jutting in from the four outer walls.
+#[derive(Debug, Clone, Copy)]
+pub struct OuterProtrusions;
+
+impl BoardFilter for OuterProtrusions {
+    fn apply(
+        &self,
+        rng: &mut dyn RngCore,
+        config: &GenerationConfig,
+        board: &mut Board,
+        occupied: &mut BTreeSet<Position>,
+        _potential_targets: &mut Vec<Position>,
+    ) {
+        outer_wall_protrusions(rng, config, board, occupied);
+    }
+}
+
+/// Scatters `config.target_density` times the side length (rounded) corner walls, each a potential
+/// target, into each of the board's four quadrants, plus one more if there's still room; see
+/// [`Generator::generate_board`].
+#[derive(Debug, Clone, Copy)]
+pub struct QuadrantCornerWalls;
+
+impl BoardFilter for QuadrantCornerWalls {
+    fn apply(
+        &self,
+        rng: &mut dyn RngCore,
+        config: &GenerationConfig,
+        board: &mut Board,
+        occupied: &mut BTreeSet<Position>,
+        potential_targets: &mut Vec<Position>,
+    ) {
+        quadrant_corner_walls(rng, config, board, occupied, potential_targets);
+    }
+}
+
+/// Grows short, connected wall segments via a momentum-biased random walk instead of scattering
+/// isolated corner walls; an alternative to [`QuadrantCornerWalls`] for more maze-like boards. See
+/// [`Generator::generate_board_corridor`].
+#[derive(Debug, Clone, Copy)]
+pub struct CorridorWalls;
+
+impl BoardFilter for CorridorWalls {
+    fn apply(
+        &self,
+        rng: &mut dyn RngCore,
+        config: &GenerationConfig,
+        board: &mut Board,
+        occupied: &mut BTreeSet<Position>,
+        potential_targets: &mut Vec<Position>,
+    ) {
+        corridor_walls(rng, config, board, occupied, potential_targets);
+    }
+}
+
+/// Closes off the board with an outer wall on all four sides. The last filter of the default
+/// pipeline, so that the filters before it don't have to treat the border as a special case.
+#[derive(Debug, Clone, Copy)]
+pub struct WallEnclosure;
+
+impl BoardFilter for WallEnclosure {
+    fn apply(
+        &self,
+        _rng: &mut dyn RngCore,
+        _config: &GenerationConfig,
+        board: &mut Board,
+        _occupied: &mut BTreeSet<Position>,
+        _potential_targets: &mut Vec<Position>,
+    ) {
+        *board = mem::take(board).wall_enclosure();
+    }
+}
+
+/// [`Generator::generate_board`]'s default filter pipeline.
+fn default_filters() -> Vec<Box<dyn BoardFilter>> {
+    vec![
+        Box::new(CenterWalls),
+        Box::new(OuterProtrusions),
+        Box::new(QuadrantCornerWalls),
+        Box::new(WallEnclosure),
+    ]
+}
+
+/// A rectangular region of a board's interior, used by [`Generator::generate_board_bsp`]'s
+/// recursive partitioning.
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    col: PositionEncoding,
+    row: PositionEncoding,
+    width: PositionEncoding,
+    height: PositionEncoding,
+}
+
+impl Rect {
+    fn area(&self) -> PositionEncoding {
+        self.width * self.height
+    }
+
+    /// The field nearest the center of this rectangle.
+    fn centroid(&self) -> Position {
+        Position::new(self.col + self.width / 2, self.row + self.height / 2)
+    }
+
+    /// Splits this rectangle along its longer axis at a random cut, refusing to produce a
+    /// sub-rectangle narrower than `min_cell` along that axis.
+    fn split(&self, rng: &mut impl Rng, min_cell: PositionEncoding) -> Option<(Rect, Rect)> {
+        if self.width >= self.height {
+            if self.width < 2 * min_cell {
+                return None;
+            }
+            let cut = rng.gen_range(min_cell..=(self.width - min_cell));
+            Some((
+                Rect { width: cut, ..*self },
+                Rect { col: self.col + cut, width: self.width - cut, ..*self },
+            ))
+        } else {
+            if self.height < 2 * min_cell {
+                return None;
+            }
+            let cut = rng.gen_range(min_cell..=(self.height - min_cell));
+            Some((
+                Rect { height: cut, ..*self },
+                Rect { row: self.row + cut, height: self.height - cut, ..*self },
+            ))
+        }
+    }
+}
+
+/// The Manhattan distance between two positions.
+fn manhattan_distance(a: Position, b: Position) -> PositionEncoding {
+    let col_diff = a.column().max(b.column()) - a.column().min(b.column());
+    let row_diff = a.row().max(b.row()) - a.row().min(b.row());
+    col_diff + row_diff
+}
+
+/// Breadth-first search over robot configurations from `start`, capped at `max_moves` moves,
+/// returning the depth at which `round`'s target is first reached.
+///
+/// Unlike [`Round::solve`](crate::Round::solve), which has no move cap and only stops early once it
+/// reaches the target, this gives up past `max_moves` and dedupes visited states in a
+/// `BTreeSet` rather than a `HashMap`, so that candidates rejection-sampled for difficulty stay
+/// cheap to discard when they're unsolvable within budget.
+fn optimal_move_count(round: &Round, start: &RobotPositions, max_moves: usize) -> Option<usize> {
+    if round.target_reached(start) {
+        return Some(0);
+    }
+
+    let mut visited = BTreeSet::new();
+    visited.insert(start.clone());
+    let mut frontier = vec![start.clone()];
+
+    for depth in 1..=max_moves {
+        let mut next_frontier = Vec::new();
+        for positions in &frontier {
+            for (next, _) in positions.reachable_positions(round.board()) {
+                if !visited.insert(next.clone()) {
+                    continue;
+                }
+                if round.target_reached(&next) {
+                    return Some(depth);
+                }
+                next_frontier.push(next);
+            }
+        }
+        frontier = next_frontier;
+    }
+
+    None
+}
+
+/// Adds `pos` and its 3x3 neighborhood (clipped to the board) to `occupied`.
+///
+/// Shared by [`Generator::add_occupied_field`] and the [`BoardFilter`] implementations, which don't
+/// have a `Generator` to call that method on.
+fn dilate_occupied(occupied: &mut BTreeSet<Position>, side_length: PositionEncoding, pos: Position) {
+    let additions: Vec<(_, fn(_, _) -> _)> = vec![
+        (1, PositionEncoding::checked_sub),
+        (0, PositionEncoding::checked_add),
+        (1, PositionEncoding::checked_add),
+    ];
+    for (col_add, row_add) in additions.iter().cartesian_product(&additions) {
+        let col = match col_add.1(pos.column(), col_add.0) {
+            Some(col) if col < side_length => col,
+            _ => continue,
+        };
+        let row = match row_add.1(pos.row(), row_add.0) {
+            Some(row) if row < side_length => row,
+            _ => continue,
+        };
+        occupied.insert(Position::new(col, row));
+    }
+}
+
+/// Samples a [`Direction`], favoring each one in proportion to `config.corner_wall_weights`.
+///
+/// Shared by [`place_corner_wall`] and [`corridor_walls`].
+///
+/// # Panics
+/// Panics if `config.corner_wall_weights` are all zero or negative.
+fn weighted_direction(rng: &mut dyn RngCore, config: &GenerationConfig) -> Direction {
+    *crate::DIRECTIONS
+        .choose_weighted(rng, |dir| {
+            let idx = crate::DIRECTIONS.iter().position(|d| d == dir).unwrap();
+            config.corner_wall_weights[idx]
+        })
+        .expect("corner_wall_weights has a positive entry")
+}
+
+/// Adds a random corner wall to the field at `pos`, favoring each [`Direction`] in proportion to
+/// `config.corner_wall_weights`.
+///
+/// Shared by [`Generator::walls_around_field`] and the [`BoardFilter`] implementations.
+///
+/// # Panics
+/// May panic if `pos` is at the edge of the board, or if `config.corner_wall_weights` are all
+/// zero or negative.
+fn place_corner_wall(
+    rng: &mut dyn RngCore,
+    config: &GenerationConfig,
+    board: &mut Board,
+    pos: Position,
+) {
+    match weighted_direction(rng, config) {
+        Direction::Up => {
+            let above = Position::new(pos.column(), pos.row() - 1);
+            board[above].down = true;
+            board[pos].right = true;
+        }
+        Direction::Right => {
+            board[pos].right = true;
+            board[pos].down = true;
+        }
+        Direction::Down => {
+            let left = Position::new(pos.column() - 1, pos.row());
+            board[pos].down = true;
+            board[left].right = true;
+        }
+        Direction::Left => {
+            let left = Position::new(pos.column() - 1, pos.row());
+            let above = Position::new(pos.column(), pos.row() - 1);
+            board[left].right = true;
+            board[above].down = true;
+        }
+    }
+}
+
+/// Adds walls protruding from the outer walls to the board: `config.protrusion_density` times the
+/// side length (rounded up, at least one) per outer wall.
+///
+/// Shared by [`Generator::add_outer_wall_protrusions`] and [`OuterProtrusions`].
+fn outer_wall_protrusions(
+    rng: &mut dyn RngCore,
+    config: &GenerationConfig,
+    board: &mut Board,
+    occupied: &mut BTreeSet<Position>,
+) {
+    let side_length = board.side_length();
+    let walls = board.get_mut_walls();
+    let num_per_wall = ((side_length as f64 * config.protrusion_density).ceil() as usize).max(1);
+    let segment_length = side_length as usize / num_per_wall;
+    let is_odd_length = side_length % 2 == 1;
+
+    // Get the indices of the fields for which walls will be set.
+    let get_indices = |rng: &mut dyn RngCore| {
+        let mut indices = Vec::with_capacity(num_per_wall);
+        let mut segment_sum = 0;
+        for n in 0..num_per_wall {
+            let mut len = segment_length;
+            if is_odd_length && (num_per_wall - n) % 2 == 1 {
+                len += 1;
+            }
+
+            // Exclude the first field of the first segment.
+            let start = segment_sum + (n == 0) as usize;
+
+            segment_sum += len;
+
+            let mut end = segment_sum - 1;
+            if n == num_per_wall - 1 {
+                // Exclude the last two fields of the last segment.
+                end = side_length as usize - 2;
+            }
+
+            indices.push(rng.gen_range(start..end))
+        }
+        indices
+    };
+
+    // Set protrusions at the top and bottom.
+    let other_idx = [0, walls.width() - 1];
+    for &row in &other_idx {
+        for col in get_indices(rng) {
+            walls[col][row].right = true;
+            dilate_occupied(
+                occupied,
+                side_length,
+                Position::new(col as PositionEncoding, row as PositionEncoding),
+            );
+        }
+    }
+
+    // Set protrusions at walls on the left and on the right.
+    for &col in &other_idx {
+        for row in get_indices(rng) {
+            walls[col][row].down = true;
+            dilate_occupied(
+                occupied,
+                side_length,
+                Position::new(col as PositionEncoding, row as PositionEncoding),
+            );
+        }
+    }
+}
+
+/// Scatters `config.target_density` times the side length (rounded) corner walls into each board
+/// quadrant, recording each as a potential target, plus one more wherever there's still room in
+/// the interior.
+///
+/// Shared by [`Generator::generate_board`] (by way of [`QuadrantCornerWalls`]).
+fn quadrant_corner_walls(
+    rng: &mut dyn RngCore,
+    config: &GenerationConfig,
+    board: &mut Board,
+    occupied: &mut BTreeSet<Position>,
+    potential_targets: &mut Vec<Position>,
+) {
+    let side_length = board.side_length();
+    let first_quad_len = side_length / 2;
+    let mut other_quad_len = first_quad_len;
+    if side_length % 2 == 1 {
+        other_quad_len += 1
+    }
+    // The parts of the quadrants in which walls will be generated in the form
+    // `((col, row), (width, height))`.
+    let quadrants = vec![
+        ((1, 1), (first_quad_len - 1, first_quad_len - 1)),
+        (
+            (1, first_quad_len),
+            (first_quad_len - 1, other_quad_len - 1),
+        ),
+        (
+            (first_quad_len, 1),
+            (other_quad_len - 1, first_quad_len - 1),
+        ),
+        (
+            (first_quad_len, first_quad_len),
+            (other_quad_len - 1, other_quad_len - 1),
+        ),
+    ];
+
+    let fields = |occupied: &BTreeSet<Position>, ((col, row), (width, height))| {
+        (col..(col + width))
+            .cartesian_product(row..(row + height))
+            .map(Position::from)
+            .collect::<BTreeSet<_>>()
+            .difference(occupied)
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let fields_per_quad = (side_length as f64 * config.target_density).round() as usize;
+    for quad in quadrants {
+        for _ in 0..fields_per_quad {
+            let chosen = match fields(occupied, quad).choose(rng) {
+                Some(field) => *field,
+                None => break,
+            };
+            place_corner_wall(rng, config, board, chosen);
+
+            potential_targets.push(chosen);
+            dilate_occupied(occupied, side_length, chosen);
+        }
+    }
+
+    // Add one more corner wall if there is any space left.
+    let open_fields = fields(occupied, ((1, 1), (side_length - 2, side_length - 2)));
+    if let Some(&field) = open_fields.choose(rng) {
+        place_corner_wall(rng, config, board, field);
+        potential_targets.push(field);
+    }
+}
+
+/// Steps one field from `pos` in `dir`, or returns `None` if that would leave `side_length`'s
+/// interior (the outer ring of fields, reserved for [`outer_wall_protrusions`]).
+///
+/// Shared by [`Generator::step_within_interior`] and [`corridor_walls`].
+fn step_within_interior(
+    side_length: PositionEncoding,
+    pos: Position,
+    dir: Direction,
+) -> Option<Position> {
+    let interior = 1..(side_length - 1);
+    let (col, row) = match dir {
+        Direction::Up => (pos.column(), pos.row().checked_sub(1)?),
+        Direction::Down => (pos.column(), pos.row() + 1),
+        Direction::Left => (pos.column().checked_sub(1)?, pos.row()),
+        Direction::Right => (pos.column() + 1, pos.row()),
+    };
+
+    if interior.contains(&col) && interior.contains(&row) {
+        Some(Position::new(col, row))
+    } else {
+        None
+    }
+}
+
+/// Picks a uniformly random interior field not already in `occupied`, giving up after
+/// `side_length^2` attempts (in case `occupied` has filled the whole interior).
+fn random_free_field(
+    rng: &mut dyn RngCore,
+    side_length: PositionEncoding,
+    occupied: &BTreeSet<Position>,
+) -> Option<Position> {
+    let interior = 1..(side_length - 1);
+    for _ in 0..(side_length as usize * side_length as usize) {
+        let pos = Position::new(rng.gen_range(interior.clone()), rng.gen_range(interior.clone()));
+        if !occupied.contains(&pos) {
+            return Some(pos);
+        }
+    }
+    None
+}
+
+/// Grows short, connected wall segments by walking from a random field, placing a corner wall at
+/// every step with [`place_corner_wall`] and advancing in [`weighted_direction`] with probability
+/// `1.0 - config.momentum_prob`, or repeating the previous direction otherwise. A walk stops once
+/// it has taken `side_length` steps, would re-enter an already-occupied field, or would leave the
+/// board's interior; its last field is recorded as a potential target, the way
+/// [`quadrant_corner_walls`] records every corner wall it places.
+///
+/// Runs as many walks as [`quadrant_corner_walls`] would scatter corner walls across all four
+/// quadrants combined, so [`GenerationStrategy::Corridor`] and [`GenerationStrategy::Quadrant`]
+/// end up with comparable target counts.
+///
+/// Shared by [`CorridorWalls`] and [`Generator::generate_board_corridor`].
+fn corridor_walls(
+    rng: &mut dyn RngCore,
+    config: &GenerationConfig,
+    board: &mut Board,
+    occupied: &mut BTreeSet<Position>,
+    potential_targets: &mut Vec<Position>,
+) {
+    let side_length = board.side_length();
+    let walk_count = ((side_length as f64 * config.target_density).round() as usize * 4).max(1);
+
+    for _ in 0..walk_count {
+        let mut pos = match random_free_field(rng, side_length, occupied) {
+            Some(pos) => pos,
+            None => break,
+        };
+        let mut dir = weighted_direction(rng, config);
+        let mut last = pos;
+        let mut walked = Vec::new();
+
+        for _ in 0..side_length {
+            if occupied.contains(&pos) || walked.contains(&pos) {
+                break;
+            }
+
+            place_corner_wall(rng, config, board, pos);
+            walked.push(pos);
+            last = pos;
+
+            if !rng.gen_bool(config.momentum_prob) {
+                dir = weighted_direction(rng, config);
+            }
+            match step_within_interior(side_length, pos, dir) {
+                Some(next) => pos = next,
+                None => break,
+            }
+        }
+
+        // Only claim the walk's neighborhood once it is done, so its own steps don't stop it.
+        for &field in &walked {
+            dilate_occupied(occupied, side_length, field);
+        }
+        potential_targets.push(last);
+    }
+}
+
 /// A board generator to create boards of different sizes and configurations.
 #[derive(Debug)]
 pub struct Generator {
@@ -17,10 +615,12 @@ pub struct Generator {
     side_length: PositionEncoding,
     potential_targets: Vec<Position>,
     occupied_fields: BTreeSet<Position>,
+    filters: Vec<Box<dyn BoardFilter>>,
+    config: GenerationConfig,
 }
 
 impl Generator {
-    /// Creates a new generator with a random state.
+    /// Creates a new generator with a random state and [`GenerationConfig::default`].
     ///
     /// # Panics
     /// Panics if `side_length` is less than `3`.
@@ -30,10 +630,12 @@ impl Generator {
             side_length,
             potential_targets: Vec::new(),
             occupied_fields: BTreeSet::new(),
+            filters: default_filters(),
+            config: GenerationConfig::default(),
         }
     }
 
-    /// Creates a new generator initialized with `seed`.
+    /// Creates a new generator initialized with `seed` and [`GenerationConfig::default`].
     ///
     /// The generator was implemented in a way that focuses on generatin boards with a `side_length`
     /// greater than 6.
@@ -46,14 +648,140 @@ impl Generator {
             side_length,
             potential_targets: Vec::new(),
             occupied_fields: BTreeSet::new(),
+            filters: default_filters(),
+            config: GenerationConfig::default(),
         }
     }
 
+    /// Creates a new generator initialized with `seed` and a custom [`GenerationConfig`], for
+    /// producing easy or hard benchmark boards from the same code path as [`from_seed`].
+    ///
+    /// [`from_seed`]: Self::from_seed
+    ///
+    /// # Panics
+    /// Panics if `side_length` is less than `3`.
+    pub fn with_config(
+        seed: u128,
+        side_length: PositionEncoding,
+        config: GenerationConfig,
+    ) -> Self {
+        Self { config, ..Self::from_seed(seed, side_length) }
+    }
+
+    /// Returns the ordered pipeline of filters [`generate_board`](Self::generate_board) runs.
+    ///
+    /// Defaults to [`CenterWalls`], [`OuterProtrusions`], [`QuadrantCornerWalls`], then
+    /// [`WallEnclosure`], in that order. Push, insert, reorder, or remove entries to customize board
+    /// generation without forking `generate_board` itself.
+    pub fn filters_mut(&mut self) -> &mut Vec<Box<dyn BoardFilter>> {
+        &mut self.filters
+    }
+
+    /// Returns this generator's [`GenerationConfig`] for tuning board sparseness/density.
+    ///
+    /// Every default filter (and [`generate_random_walk_board`](Self::generate_random_walk_board)
+    /// and [`generate_board_bsp`](Self::generate_board_bsp)) reads it fresh on every call, so
+    /// changes take effect on the very next board generated.
+    pub fn config_mut(&mut self) -> &mut GenerationConfig {
+        &mut self.config
+    }
+
     /// Generates a new game with a board and targets.
     ///
     /// Some targets may be on the same field.
     pub fn generate_game(&mut self) -> Game {
         let board = self.generate_board();
+        Game::new(board, self.assign_targets())
+    }
+
+    /// Generates a new game like [`generate_game`](Self::generate_game), but only accepts one
+    /// whose optimal solution length falls within `[min_moves, max_moves]`, resampling up to
+    /// `max_attempts` times.
+    ///
+    /// Each attempt generates a whole new game, draws a random target from it and a random legal
+    /// starting layout, and solves that candidate `Round` with a breadth-first search over robot
+    /// configurations (see [`optimal_move_count`]) capped at `max_moves` moves — not
+    /// [`BreadthFirst`](https://docs.rs/ricochet_solver), which this crate can't depend on, but the
+    /// same idea, self-contained so boards can be rejection-sampled for difficulty right where
+    /// they're generated. Returns `None` if no attempt lands in range within the budget.
+    pub fn generate_game_with_difficulty(
+        &mut self,
+        min_moves: usize,
+        max_moves: usize,
+        max_attempts: usize,
+    ) -> Option<Game> {
+        for _ in 0..max_attempts {
+            let game = self.generate_game();
+            if game.targets().is_empty() {
+                continue;
+            }
+
+            let target_index = self.rng.gen_range(0..game.targets().len());
+            let (&target, &target_position) = game
+                .targets()
+                .iter()
+                .nth(target_index)
+                .expect("target_index is within bounds of the non-empty target map");
+
+            let round = Round::new(game.board().clone(), target, target_position);
+            let start = self.random_robot_positions();
+
+            match optimal_move_count(&round, &start, max_moves) {
+                Some(depth) if (min_moves..=max_moves).contains(&depth) => return Some(game),
+                _ => continue,
+            }
+        }
+
+        None
+    }
+
+    /// Draws random legal starting positions, resampling as long as a robot lands inside the
+    /// enclosed center block.
+    ///
+    /// Mirrors `ricochet_solver::RoundGenerator`'s layout sampling, duplicated here since this
+    /// crate can't depend on `ricochet_solver`.
+    fn random_robot_positions(&mut self) -> RobotPositions {
+        loop {
+            let range = 0..self.side_length;
+            let mut next_coordinate =
+                || (self.rng.gen_range(range.clone()), self.rng.gen_range(range.clone()));
+            let positions = [
+                next_coordinate(),
+                next_coordinate(),
+                next_coordinate(),
+                next_coordinate(),
+            ];
+
+            if self.side_length >= self.config.center_walls_threshold {
+                let start = self.side_length / 2 - 1;
+                let end = start + 1;
+                if positions
+                    .iter()
+                    .any(|(c, r)| (start..=end).contains(c) && (start..=end).contains(r))
+                {
+                    continue;
+                }
+            }
+
+            return RobotPositions::from_tuples(&positions);
+        }
+    }
+
+    /// Generates a new game whose walls come from a momentum-biased random walk rather than
+    /// [`generate_board`](Self::generate_board)'s per-quadrant field sampling.
+    ///
+    /// Some targets may be on the same field.
+    ///
+    /// # Panics
+    /// Panics if `side_length` is less than `3`, or if `momentum_prob` isn't within `0.0..=1.0`.
+    pub fn generate_random_walk_game(&mut self, momentum_prob: f64, segments: usize) -> Game {
+        let board = self.generate_random_walk_board(momentum_prob, segments);
+        Game::new(board, self.assign_targets())
+    }
+
+    /// Picks a target position for every `Target`, drawing each one independently and uniformly
+    /// from `self.potential_targets`, so several targets may land on the same field.
+    fn assign_targets(&mut self) -> BTreeMap<crate::Target, Position> {
         let mut unused = self.potential_targets.clone();
         let mut targets = BTreeMap::new();
         for &target in &crate::TARGETS {
@@ -63,17 +791,93 @@ impl Generator {
             let pos = *unused.choose(&mut self.rng).unwrap();
             targets.insert(target, pos);
         }
-
-        Game::new(board, targets)
+        targets
     }
 
-    /// Generates a new board and updates potential targets.
+    /// Generates a new board and updates potential targets by running [`filters_mut`]'s pipeline
+    /// in order, then discarding any potential target a robot can't actually reach (see
+    /// [`filter_unreachable_targets`](Self::filter_unreachable_targets)).
+    ///
+    /// [`filters_mut`]: Self::filters_mut
     pub fn generate_board(&mut self) -> Board {
         let mut base = Board::new_empty(self.side_length);
         self.potential_targets = Vec::new();
         self.occupied_fields = BTreeSet::new();
 
-        if self.side_length >= CENTER_WALLS_FROM_SIDE_LENGTH {
+        for filter in &self.filters {
+            filter.apply(
+                &mut self.rng,
+                &self.config,
+                &mut base,
+                &mut self.occupied_fields,
+                &mut self.potential_targets,
+            );
+        }
+
+        self.filter_unreachable_targets(&base);
+        base
+    }
+
+    /// Generates a new game whose corner walls grow from [`corridor_walls`]'s momentum-biased
+    /// random walk rather than [`generate_board`](Self::generate_board)'s per-quadrant field
+    /// sampling, for longer forced slides.
+    ///
+    /// Some targets may be on the same field.
+    pub fn generate_corridor_game(&mut self) -> Game {
+        let board = self.generate_board_corridor();
+        Game::new(board, self.assign_targets())
+    }
+
+    /// Generates a board with [`CorridorWalls`] in place of [`QuadrantCornerWalls`], otherwise
+    /// running the same [`CenterWalls`], [`OuterProtrusions`], [`WallEnclosure`] steps as
+    /// [`generate_board`](Self::generate_board)'s default pipeline. Ignores any custom
+    /// [`filters_mut`](Self::filters_mut) pipeline, the same way
+    /// [`generate_board_bsp`](Self::generate_board_bsp) does.
+    pub fn generate_board_corridor(&mut self) -> Board {
+        let mut base = Board::new_empty(self.side_length);
+        self.potential_targets = Vec::new();
+        self.occupied_fields = BTreeSet::new();
+
+        let pipeline: [&dyn BoardFilter; 4] =
+            [&CenterWalls, &OuterProtrusions, &CorridorWalls, &WallEnclosure];
+        for filter in pipeline {
+            filter.apply(
+                &mut self.rng,
+                &self.config,
+                &mut base,
+                &mut self.occupied_fields,
+                &mut self.potential_targets,
+            );
+        }
+
+        self.filter_unreachable_targets(&base);
+        base
+    }
+
+    /// Generates a board by walking a momentum-biased random path across the interior and laying a
+    /// corner wall at every unoccupied field it steps onto.
+    ///
+    /// The walker starts at a random interior cell and takes `segments` steps, reusing its previous
+    /// direction with probability `momentum_prob` and otherwise picking a new one uniformly from
+    /// [`DIRECTIONS`](crate::DIRECTIONS). Biasing towards the previous direction makes the walker
+    /// favor runs of steps in the same direction, which lays out long, corridor-like wall
+    /// structures instead of [`generate_board`](Self::generate_board)'s more evenly scattered
+    /// corners. Steps that would leave the interior re-roll a new direction in place rather than
+    /// ending the walk early.
+    ///
+    /// # Panics
+    /// Panics if `side_length` is less than `3`, or if `momentum_prob` isn't within `0.0..=1.0`.
+    pub fn generate_random_walk_board(&mut self, momentum_prob: f64, segments: usize) -> Board {
+        assert!(
+            (0.0..=1.0).contains(&momentum_prob),
+            "momentum_prob has to be within 0.0..=1.0"
+        );
+
+        let mut base = Board::new_empty(self.side_length);
+        self.potential_targets = Vec::new();
+        self.occupied_fields = BTreeSet::new();
+
+        if self.side_length >= self.config.center_walls_threshold {
             base = base.set_center_walls();
             let f = self.side_length / 2 - 1;
             for (col_add, row_add) in [0, 1].iter().cartesian_product(&[0, 1]) {
@@ -83,178 +887,244 @@ impl Generator {
 
         self.add_outer_wall_protrusions(&mut base);
 
-        let first_quad_len = self.side_length / 2;
-        let mut other_quad_len = first_quad_len;
-        if self.side_length % 2 == 1 {
-            other_quad_len += 1
-        }
-        // The parts of the quadrants in which walls will be generated in the form
-        // `((col, row), (width, height))`.
-        let quadrants = vec![
-            ((1, 1), (first_quad_len - 1, first_quad_len - 1)),
-            (
-                (1, first_quad_len),
-                (first_quad_len - 1, other_quad_len - 1),
-            ),
-            (
-                (first_quad_len, 1),
-                (other_quad_len - 1, first_quad_len - 1),
-            ),
-            (
-                (first_quad_len, first_quad_len),
-                (other_quad_len - 1, other_quad_len - 1),
-            ),
-        ];
-
-        let fields = |occupied: &BTreeSet<Position>, ((col, row), (width, height))| {
-            (col..(col + width))
-                .cartesian_product(row..(row + height))
-                .map(Position::from)
-                .collect::<BTreeSet<_>>()
-                .difference(occupied)
-                .cloned()
-                .collect::<Vec<_>>()
-        };
+        let interior = 1..(self.side_length - 1);
+        let mut pos = Position::new(
+            self.rng.gen_range(interior.clone()),
+            self.rng.gen_range(interior.clone()),
+        );
+        let mut dir = *crate::DIRECTIONS.choose(&mut self.rng).unwrap();
 
-        let fields_per_quad = (self.side_length as f64 / 4.0).round() as usize;
-        for quad in quadrants {
-            for _ in 0..fields_per_quad {
-                let chosen = match fields(&self.occupied_fields, quad).choose(&mut self.rng) {
-                    Some(field) => *field,
-                    None => break,
-                };
-                self.walls_around_field(&mut base, chosen);
-
-                self.potential_targets.push(chosen);
-                self.add_occupied_field(chosen);
+        for _ in 0..segments {
+            if !self.rng.gen_bool(momentum_prob) {
+                dir = *crate::DIRECTIONS.choose(&mut self.rng).unwrap();
             }
-        }
 
-        // Add one more corner wall if there is any space left.
-        let open_fields = fields(
-            &self.occupied_fields,
-            ((1, 1), (self.side_length - 2, self.side_length - 2)),
-        );
-        if let Some(&field) = open_fields.choose(&mut self.rng) {
-            self.walls_around_field(&mut base, field);
-            self.potential_targets.push(field);
+            if !self.occupied_fields.contains(&pos) {
+                self.walls_around_field(&mut base, pos);
+                self.potential_targets.push(pos);
+                self.add_occupied_field(pos);
+            }
+
+            match self.step_within_interior(pos, dir) {
+                Some(next) => pos = next,
+                None => dir = *crate::DIRECTIONS.choose(&mut self.rng).unwrap(),
+            }
         }
 
         base = base.wall_enclosure();
+        self.filter_unreachable_targets(&base);
         base
     }
 
-    /// Adds a random corner wall to the field at `pos`.
+    /// Generates a new game whose walls come from recursive binary space partitioning rather than
+    /// [`generate_board`](Self::generate_board)'s per-quadrant field sampling, using
+    /// [`DEFAULT_BSP_MIN_CELL`] as the minimum sub-rectangle size.
+    ///
+    /// Some targets may be on the same field.
     ///
     /// # Panics
-    /// May panic if `pos` is at the edge of the board.
-    fn walls_around_field(&mut self, board: &mut Board, pos: Position) {
-        let dirs = crate::DIRECTIONS;
-        match dirs.choose(&mut self.rng).unwrap() {
-            Direction::Up => {
-                let above = Position::new(pos.column(), pos.row() - 1);
-                board[above].down = true;
-                board[pos].right = true;
-            }
-            Direction::Right => {
-                board[pos].right = true;
-                board[pos].down = true;
-            }
-            Direction::Down => {
-                let left = Position::new(pos.column() - 1, pos.row());
-                board[pos].down = true;
-                board[left].right = true;
-            }
-            Direction::Left => {
-                let left = Position::new(pos.column() - 1, pos.row());
-                let above = Position::new(pos.column(), pos.row() - 1);
-                board[left].right = true;
-                board[above].down = true;
-            }
+    /// Panics if `side_length` is less than `3`.
+    pub fn generate_bsp_game(&mut self) -> Game {
+        let board = self.generate_board_bsp(DEFAULT_BSP_MIN_CELL);
+        Game::new(board, self.assign_targets())
+    }
+
+    /// Generates a board using `strategy` to distribute target fields.
+    pub fn generate_board_with_strategy(&mut self, strategy: GenerationStrategy) -> Board {
+        match strategy {
+            GenerationStrategy::Quadrant => self.generate_board(),
+            GenerationStrategy::Bsp => self.generate_board_bsp(DEFAULT_BSP_MIN_CELL),
+            GenerationStrategy::Corridor => self.generate_board_corridor(),
         }
     }
 
-    /// Adds walls protruding from the outer walls to the board.
-    fn add_outer_wall_protrusions(&mut self, board: &mut Board) {
-        let walls = board.get_mut_walls();
-        let num_per_wall = (self.side_length as usize + 7) / 8;
-        let segment_length = self.side_length as usize / num_per_wall;
-        let is_odd_length = self.side_length % 2 == 1;
-
-        // Get the indices of the fields for which walls will be set.
-        let get_indices = |generator: &mut Self| {
-            let mut indices = Vec::with_capacity(num_per_wall);
-            let mut segment_sum = 0;
-            for n in 0..num_per_wall {
-                let mut len = segment_length;
-                if is_odd_length && (num_per_wall - n) % 2 == 1 {
-                    len += 1;
-                }
+    /// Generates a board by recursively splitting the interior into sub-rectangles and placing one
+    /// corner wall near the centroid of each, for target placement that stays spatially even
+    /// independent of board size.
+    ///
+    /// Starting from the whole interior `((1, 1), (side_length - 2, side_length - 2))`, the largest
+    /// remaining sub-rectangle is repeatedly split along its longer axis at a randomly positioned
+    /// cut until there are at least [`TARGETS`](crate::TARGETS)`.len()` of them, or the largest
+    /// remaining sub-rectangle is too small to split without dropping below `min_cell` along its
+    /// longer axis. One corner wall is then placed in each sub-rectangle, as close to its centroid
+    /// as possible while skipping fields already in `self.occupied_fields` (and their
+    /// neighborhoods, added there by [`add_occupied_field`](Self::add_occupied_field)).
+    ///
+    /// # Panics
+    /// Panics if `side_length` is less than `3`.
+    pub fn generate_board_bsp(&mut self, min_cell: PositionEncoding) -> Board {
+        let mut base = Board::new_empty(self.side_length);
+        self.potential_targets = Vec::new();
+        self.occupied_fields = BTreeSet::new();
 
-                // Exclude the first field of the first segment.
-                let start = segment_sum + (n == 0) as usize;
+        if self.side_length >= self.config.center_walls_threshold {
+            base = base.set_center_walls();
+            let f = self.side_length / 2 - 1;
+            for (col_add, row_add) in [0, 1].iter().cartesian_product(&[0, 1]) {
+                self.add_occupied_field(Position::new(f + col_add, f + row_add));
+            }
+        }
 
-                segment_sum += len;
+        self.add_outer_wall_protrusions(&mut base);
 
-                let mut end = segment_sum - 1;
-                if n == num_per_wall - 1 {
-                    // Exclude the last two fields of the last segment.
-                    end = generator.side_length as usize - 2;
-                }
+        let mut leaves = vec![Rect {
+            col: 1,
+            row: 1,
+            width: self.side_length - 2,
+            height: self.side_length - 2,
+        }];
 
-                indices.push(generator.rng.gen_range(start..end))
-            }
-            indices
-        };
+        while leaves.len() < crate::TARGETS.len() {
+            let largest_idx = leaves
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, rect)| rect.area())
+                .map(|(idx, _)| idx)
+                .unwrap();
 
-        // Set protrusions at the top and bottom.
-        let other_idx = [0, walls.len() - 1];
-        for &row in &other_idx {
-            for col in get_indices(self) {
-                walls[col][row].right = true;
-                self.add_occupied_field(Position::new(
-                    col as PositionEncoding,
-                    row as PositionEncoding,
-                ));
+            match leaves[largest_idx].split(&mut self.rng, min_cell) {
+                Some((a, b)) => {
+                    leaves[largest_idx] = a;
+                    leaves.push(b);
+                }
+                // The largest remaining leaf is too small to split further.
+                None => break,
             }
         }
 
-        // Set protrusions at walls on the left and on the right.
-        for &col in &other_idx {
-            for row in get_indices(self) {
-                walls[col][row].down = true;
-                self.add_occupied_field(Position::new(
-                    col as PositionEncoding,
-                    row as PositionEncoding,
-                ));
-            }
+        for leaf in leaves {
+            let candidates = (leaf.col..(leaf.col + leaf.width))
+                .cartesian_product(leaf.row..(leaf.row + leaf.height))
+                .map(Position::from)
+                .collect::<BTreeSet<_>>()
+                .difference(&self.occupied_fields)
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let centroid = leaf.centroid();
+            let chosen = match candidates
+                .into_iter()
+                .min_by_key(|&pos| manhattan_distance(pos, centroid))
+            {
+                Some(pos) => pos,
+                None => continue,
+            };
+
+            self.walls_around_field(&mut base, chosen);
+            self.potential_targets.push(chosen);
+            self.add_occupied_field(chosen);
         }
+
+        base = base.wall_enclosure();
+        self.filter_unreachable_targets(&base);
+        base
+    }
+
+    /// Steps one field from `pos` in `dir`, or returns `None` if that would leave the board's
+    /// interior (the outer ring of fields, reserved for [`add_outer_wall_protrusions`]).
+    fn step_within_interior(&self, pos: Position, dir: Direction) -> Option<Position> {
+        step_within_interior(self.side_length, pos, dir)
+    }
+
+    /// Adds a random corner wall to the field at `pos`.
+    ///
+    /// # Panics
+    /// May panic if `pos` is at the edge of the board.
+    fn walls_around_field(&mut self, board: &mut Board, pos: Position) {
+        place_corner_wall(&mut self.rng, &self.config, board, pos);
+    }
+
+    /// Adds walls protruding from the outer walls to the board.
+    fn add_outer_wall_protrusions(&mut self, board: &mut Board) {
+        outer_wall_protrusions(&mut self.rng, &self.config, board, &mut self.occupied_fields);
     }
 
     /// Adds a field and its surroundings to `self.occupied_fields`.
     fn add_occupied_field(&mut self, pos: Position) {
-        let additions: Vec<(_, fn(_, _) -> _)> = vec![
-            (1, PositionEncoding::checked_sub),
-            (0, PositionEncoding::checked_add),
-            (1, PositionEncoding::checked_add),
-        ];
-        for (col_add, row_add) in additions.iter().cartesian_product(&additions) {
-            let col = match col_add.1(pos.column(), col_add.0) {
-                Some(col) if col < self.side_length => col,
-                _ => continue,
-            };
-            let row = match row_add.1(pos.row(), row_add.0) {
-                Some(row) if row < self.side_length => row,
-                _ => continue,
-            };
-            self.occupied_fields.insert(Position::new(col, row));
-        }
+        dilate_occupied(&mut self.occupied_fields, self.side_length, pos);
+    }
+
+    /// A handful of start squares spread across the board, used by
+    /// [`filter_unreachable_targets`](Self::filter_unreachable_targets) to probe its connectivity.
+    ///
+    /// The four corners are always reachable themselves (the walls generated so far never box one
+    /// in, since [`add_outer_wall_protrusions`](Self::add_outer_wall_protrusions) leaves them
+    /// alone), and between them their slide graphs cover every field a robot could come to rest on
+    /// from *any* starting layout, since every interior field is reachable from at least one edge
+    /// in an unobstructed straight line.
+    fn representative_start_squares(&self) -> [Position; 4] {
+        let last = self.side_length - 1;
+        [
+            Position::new(0, 0),
+            Position::new(last, 0),
+            Position::new(0, last),
+            Position::new(last, last),
+        ]
+    }
+
+    /// Drops every `self.potential_targets` entry a robot could never slide to a stop on, so that
+    /// [`assign_targets`](Self::assign_targets) can't hand out a field boxed in by its own corner
+    /// wall (or a protrusion) as unreachable dead space.
+    fn filter_unreachable_targets(&mut self, board: &Board) {
+        let reachable = self
+            .representative_start_squares()
+            .into_iter()
+            .flat_map(|start| board.reachable_fields(start))
+            .collect::<BTreeSet<_>>();
+
+        self.potential_targets.retain(|pos| reachable.contains(pos));
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Generator;
+    use super::{GenerationConfig, GenerationStrategy, Generator, DEFAULT_BSP_MIN_CELL};
+
+    #[test]
+    fn generate_bsp_games() {
+        let mut gen = Generator::from_seed(1234567890, 16);
+        for _ in 0..100 {
+            gen.generate_bsp_game();
+        }
+    }
+
+    #[test]
+    fn bsp_is_deterministic_per_seed() {
+        let board_one = Generator::from_seed(42, 16).generate_board_bsp(DEFAULT_BSP_MIN_CELL);
+        let board_two = Generator::from_seed(42, 16).generate_board_bsp(DEFAULT_BSP_MIN_CELL);
+        assert_eq!(*board_one.get_walls(), *board_two.get_walls());
+    }
+
+    #[test]
+    fn generate_board_with_strategy_dispatches_to_bsp() {
+        let board_one =
+            Generator::from_seed(7, 16).generate_board_with_strategy(GenerationStrategy::Bsp);
+        let board_two = Generator::from_seed(7, 16).generate_board_bsp(DEFAULT_BSP_MIN_CELL);
+        assert_eq!(*board_one.get_walls(), *board_two.get_walls());
+    }
+
+    #[test]
+    fn generate_board_with_strategy_dispatches_to_corridor() {
+        let board_one =
+            Generator::from_seed(7, 16).generate_board_with_strategy(GenerationStrategy::Corridor);
+        let board_two = Generator::from_seed(7, 16).generate_board_corridor();
+        assert_eq!(*board_one.get_walls(), *board_two.get_walls());
+    }
+
+    #[test]
+    fn corridor_is_deterministic_per_seed() {
+        let board_one = Generator::from_seed(42, 16).generate_board_corridor();
+        let board_two = Generator::from_seed(42, 16).generate_board_corridor();
+        assert_eq!(*board_one.get_walls(), *board_two.get_walls());
+    }
+
+    #[test]
+    fn generate_corridor_games() {
+        let mut gen = Generator::from_seed(1234567890, 16);
+        for _ in 0..100 {
+            gen.generate_corridor_game();
+        }
+    }
 
     #[test]
     fn different_seeds() {
@@ -263,6 +1133,78 @@ mod tests {
         assert_eq!(*board_one.get_walls(), *board_two.get_walls());
     }
 
+    #[test]
+    fn generate_board_is_deterministic_per_seed() {
+        let board_one = Generator::from_seed(42, 16).generate_board();
+        let board_two = Generator::from_seed(42, 16).generate_board();
+        assert_eq!(*board_one.get_walls(), *board_two.get_walls());
+    }
+
+    #[test]
+    fn default_config_matches_original_hard_coded_behavior() {
+        let board_one = Generator::from_seed(42, 16).generate_board();
+        let board_two =
+            Generator::with_config(42, 16, GenerationConfig::default()).generate_board();
+        assert_eq!(*board_one.get_walls(), *board_two.get_walls());
+    }
+
+    #[test]
+    fn zero_density_config_scatters_far_fewer_targets() {
+        let sparse_config = GenerationConfig {
+            target_density: 0.0,
+            protrusion_density: 0.0,
+            ..GenerationConfig::default()
+        };
+        let mut sparse = Generator::with_config(7, 16, sparse_config);
+        sparse.generate_board();
+
+        let mut dense = Generator::with_config(7, 16, GenerationConfig::default());
+        dense.generate_board();
+
+        // `quadrant_corner_walls` always adds at most one extra target if there's room left, even
+        // at zero density, so a sparse config can still end up with one target rather than none.
+        assert!(sparse.potential_targets.len() < dense.potential_targets.len());
+    }
+
+    #[test]
+    fn filters_mut_allows_dropping_a_step() {
+        let mut gen = Generator::from_seed(99, 16);
+        // `QuadrantCornerWalls` is the only default filter that records potential targets; every
+        // other one stays, so the board is still properly enclosed.
+        gen.filters_mut().remove(2);
+
+        gen.generate_board();
+
+        assert!(gen.potential_targets.is_empty());
+    }
+
+    #[test]
+    fn filters_mut_allows_appending_a_custom_step() {
+        use super::{Board, BoardFilter, Position};
+        use std::collections::BTreeSet;
+
+        #[derive(Debug)]
+        struct MarkCorner;
+        impl BoardFilter for MarkCorner {
+            fn apply(
+                &self,
+                _rng: &mut dyn rand::RngCore,
+                _config: &GenerationConfig,
+                _board: &mut Board,
+                _occupied: &mut BTreeSet<Position>,
+                potential_targets: &mut Vec<Position>,
+            ) {
+                potential_targets.push(Position::new(0, 0));
+            }
+        }
+
+        let mut gen = Generator::from_seed(99, 16);
+        gen.filters_mut().push(Box::new(MarkCorner));
+
+        gen.generate_board();
+        assert!(gen.potential_targets.contains(&Position::new(0, 0)));
+    }
+
     #[test]
     fn generate_games() {
         let mut gen = Generator::from_seed(1234567890, 16);
@@ -270,4 +1212,83 @@ mod tests {
             gen.generate_game();
         }
     }
+
+    #[test]
+    fn generated_targets_are_all_reachable_from_some_corner() {
+        let mut gen = Generator::from_seed(1234567890, 16);
+        for _ in 0..20 {
+            let game = gen.generate_game();
+            let board = game.board();
+            let last = 15;
+            let reachable: std::collections::BTreeSet<_> =
+                [(0, 0), (last, 0), (0, last), (last, last)]
+                    .into_iter()
+                    .flat_map(|(c, r)| board.reachable_fields(super::Position::new(c, r)))
+                    .collect();
+
+            for &pos in game.targets().values() {
+                assert!(reachable.contains(&pos));
+            }
+        }
+    }
+
+    #[test]
+    fn generate_random_walk_games() {
+        let mut gen = Generator::from_seed(1234567890, 16);
+        for _ in 0..100 {
+            gen.generate_random_walk_game(0.8, 40);
+        }
+    }
+
+    #[test]
+    fn random_walk_is_deterministic_per_seed() {
+        let board_one = Generator::from_seed(42, 16).generate_random_walk_board(0.7, 30);
+        let board_two = Generator::from_seed(42, 16).generate_random_walk_board(0.7, 30);
+        assert_eq!(*board_one.get_walls(), *board_two.get_walls());
+    }
+
+    #[test]
+    #[should_panic(expected = "momentum_prob")]
+    fn random_walk_rejects_out_of_range_momentum() {
+        Generator::new(16).generate_random_walk_board(1.5, 10);
+    }
+
+    #[test]
+    fn generate_game_with_difficulty_finds_a_candidate_within_lenient_bounds() {
+        let mut gen = Generator::from_seed(1234567890, 9);
+        let game = gen
+            .generate_game_with_difficulty(0, 20, 200)
+            .expect("some candidate within 0..=20 moves should turn up within 200 attempts");
+        assert!(!game.targets().is_empty());
+    }
+
+    #[test]
+    fn optimal_move_count_agrees_with_round_solve() {
+        use super::optimal_move_count;
+        use crate::{quadrant, Round};
+
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+        let game = crate::Game::from_quadrants(&quadrants);
+        let pos = crate::RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+
+        let target = crate::Target::Yellow(crate::Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let expected = round.solve(pos.clone()).expect("round is solvable").len();
+        assert_eq!(optimal_move_count(&round, &pos, expected), Some(expected));
+        assert_eq!(optimal_move_count(&round, &pos, expected - 1), None);
+    }
 }
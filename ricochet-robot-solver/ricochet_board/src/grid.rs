@@ -0,0 +1,130 @@
+//! A small, reusable flat-storage 2D grid.
+
+use std::ops;
+
+/// A bounds-checked 2D grid backed by a single flat `Vec<T>`, indexed by `(column, row)`.
+///
+/// Used to store [`Board`](crate::Board)'s walls: one contiguous allocation is friendlier to the
+/// cache than the `Vec<Vec<T>>` of per-column vectors it replaces, while
+/// [`get`](Grid::get)/[`get_mut`](Grid::get_mut) turn what used to be a panicking vector index into
+/// a checked `Option`. Column `col` is stored as the contiguous run of `height` cells starting at
+/// `col * height`, so indexing a column still yields a plain slice, e.g. `grid[col][row]` keeps
+/// working exactly as it did when walls were a `Vec<Vec<T>>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given dimensions, with every cell set to a clone of `fill`.
+    pub fn filled(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Creates a grid from `columns`, a `Vec` of equally long columns.
+    ///
+    /// # Panics
+    /// Panics if not every column in `columns` has the same length.
+    pub(crate) fn from_columns(columns: Vec<Vec<T>>) -> Self {
+        let width = columns.len();
+        let height = columns.first().map_or(0, Vec::len);
+        assert!(
+            columns.iter().all(|column| column.len() == height),
+            "Tried to build a Grid from columns of differing lengths."
+        );
+
+        Self {
+            width,
+            height,
+            cells: columns.into_iter().flatten().collect(),
+        }
+    }
+
+    /// The number of columns in the grid.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows in the grid.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if `(col, row)` lies within the grid.
+    pub fn contains(&self, col: usize, row: usize) -> bool {
+        col < self.width && row < self.height
+    }
+
+    /// Returns a reference to the cell at `(col, row)`, or `None` if it's out of bounds.
+    pub fn get(&self, col: usize, row: usize) -> Option<&T> {
+        self.contains(col, row)
+            .then(|| &self.cells[self.index_of(col, row)])
+    }
+
+    /// Returns a mutable reference to the cell at `(col, row)`, or `None` if it's out of bounds.
+    pub fn get_mut(&mut self, col: usize, row: usize) -> Option<&mut T> {
+        if self.contains(col, row) {
+            let index = self.index_of(col, row);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    fn index_of(&self, col: usize, row: usize) -> usize {
+        col * self.height + row
+    }
+}
+
+impl<T> ops::Index<usize> for Grid<T> {
+    type Output = [T];
+
+    fn index(&self, col: usize) -> &[T] {
+        let start = col * self.height;
+        &self.cells[start..start + self.height]
+    }
+}
+
+impl<T> ops::IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, col: usize) -> &mut [T] {
+        let start = col * self.height;
+        &mut self.cells[start..start + self.height]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    #[test]
+    fn get_returns_none_out_of_bounds() {
+        let grid = Grid::filled(3, 2, 0);
+        assert_eq!(grid.get(2, 1), Some(&0));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+    }
+
+    #[test]
+    fn get_mut_writes_through() {
+        let mut grid = Grid::filled(2, 2, 0);
+        *grid.get_mut(1, 0).unwrap() = 5;
+        assert_eq!(grid[1][0], 5);
+        assert_eq!(grid.get_mut(2, 0), None);
+    }
+
+    #[test]
+    fn from_columns_preserves_column_major_layout() {
+        let grid = Grid::from_columns(vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(grid[0][1], 2);
+        assert_eq!(grid[1][0], 3);
+    }
+}
@@ -22,20 +22,29 @@
 //! needed. The crate provides these parts to make board creation easier, see the
 //! [`quadrant`](quadrant) module for more information.
 
+mod ascii;
 mod draw;
+mod encoding;
 pub mod generator;
+mod grid;
 mod positions;
 pub mod quadrant;
+mod solver;
+mod svg;
 
-use std::collections::BTreeMap;
-use std::convert::{TryFrom, TryInto};
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
 use std::{fmt, ops};
 
-pub use crate::draw::draw_board;
-pub use crate::positions::{Position, PositionEncoding, RobotPositions};
-use crate::quadrant::{BoardQuadrant, Orientation, WallDirection};
+pub use crate::draw::{draw_board, draw_path, draw_round, ParseError};
+pub use crate::encoding::EncodingError;
+pub use crate::grid::Grid;
+pub use crate::positions::{MoveOutcome, Position, PositionEncoding, RobotPositions, StoppedBy};
+pub use crate::svg::{draw_html, draw_svg};
+use crate::quadrant::{BoardQuadrant, Orientation, QuadrantError, WallDirection};
 
-/// The type used to store the walls on a board.
+/// The type accepted by [`Board::new`](Board::new)/[`Board::try_new`](Board::try_new): one `Vec` of
+/// fields per column of the board.
 pub type Walls = Vec<Vec<Field>>;
 
 /// All `Direction`s a robot can move in.
@@ -102,7 +111,15 @@ pub struct Round {
 /// A ricochet robots board containing walls, but no targets.
 #[derive(Clone, Default, PartialEq, Eq)]
 pub struct Board {
-    walls: Walls,
+    walls: Grid<Field>,
+}
+
+/// Error returned by [`Board::try_new`](Board::try_new).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardError {
+    /// Not every column in the given [`Walls`](Walls) was as long as there are columns, so no
+    /// square board could be built.
+    NotSquare,
 }
 
 /// The robots identified by their color.
@@ -142,7 +159,7 @@ pub enum Symbol {
 
 /// The directions a robot can be moved in.
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Direction {
     Up,
     Down,
@@ -184,6 +201,36 @@ impl TryFrom<Target> for Robot {
     }
 }
 
+/// An identifier for a robot, independent of the fixed four-color [`Robot`] enum.
+///
+/// This is the extension point a configurable robot set would be indexed by; today every `RobotId`
+/// in use still comes from converting one of the four [`ROBOTS`]. Generalizing
+/// [`RobotPositions`](RobotPositions) itself to a variable robot count would also require reworking
+/// `ricochet_solver`'s fixed-width position keys (see its transposition table), so it isn't done
+/// here.
+pub type RobotId = u8;
+
+impl From<Robot> for RobotId {
+    fn from(robot: Robot) -> Self {
+        robot as RobotId
+    }
+}
+
+impl Target {
+    /// Returns `true` if `robot` is allowed to claim this target.
+    ///
+    /// A colored target only allows the matching color's robot; [`Target::Spiral`](Target::Spiral)
+    /// has no required color, so it allows any robot. [`Round::target_reached`](Round::target_reached)
+    /// checks this rule instead of hardcoding the color-match/`Spiral` distinction itself, so a
+    /// custom `Target` variant would only need to implement this rule to plug into a round.
+    pub fn allows(&self, robot: RobotId) -> bool {
+        match Robot::try_from(*self) {
+            Ok(required) => RobotId::from(required) == robot,
+            Err(_) => true,
+        }
+    }
+}
+
 impl fmt::Display for Robot {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let string = format!("{:?}", &self);
@@ -198,25 +245,32 @@ impl Board {
     /// # Panics
     /// Panics if not all vecs in `walls` are the same length.
     pub fn new(walls: Walls) -> Self {
-        let board_size = walls.len();
+        Self::try_new(walls).expect("Tried to create a non-square board.")
+    }
 
+    /// Create a new board with the given `walls`, or an error unless every vec in `walls` is
+    /// `walls.len()` long.
+    pub fn try_new(walls: Walls) -> Result<Self, BoardError> {
+        let board_size = walls.len();
         if walls.iter().any(|v| v.len() != board_size) {
-            panic!("Tried to create a non-square board.")
+            return Err(BoardError::NotSquare);
         }
 
-        Self { walls }
+        Ok(Self {
+            walls: Grid::from_columns(walls),
+        })
     }
 
     /// Create a new empty board with no walls with `side_length`.
     pub fn new_empty(side_length: PositionEncoding) -> Self {
         Self {
-            walls: vec![vec![Field::default(); side_length as usize]; side_length as usize],
+            walls: Grid::filled(side_length as usize, side_length as usize, Field::default()),
         }
     }
 
     /// Returns the side length of the board.
     pub fn side_length(&self) -> PositionEncoding {
-        self.walls.len() as PositionEncoding
+        self.walls.width() as PositionEncoding
     }
 
     /// Encloses the board with walls.
@@ -297,12 +351,12 @@ impl Board {
 /// Board impl containing code to interact with a board.
 impl Board {
     /// Returns a reference to the walls of the board.
-    pub fn get_walls(&self) -> &Walls {
+    pub fn get_walls(&self) -> &Grid<Field> {
         &self.walls
     }
 
     /// Returns a mutable reference to the walls of the board.
-    pub fn get_mut_walls(&mut self) -> &mut Walls {
+    pub fn get_mut_walls(&mut self) -> &mut Grid<Field> {
         &mut self.walls
     }
 
@@ -321,6 +375,104 @@ impl Board {
             }
         }
     }
+
+    /// Classifies the outcome of sliding `robot` as far as possible in `direction` from
+    /// `positions`, reporting why it didn't move at all if it's already blocked.
+    ///
+    /// A thin, `Board`-first wrapper around [`RobotPositions::try_move`](RobotPositions::try_move)
+    /// for callers that think of movement as a property of the board rather than of the robots.
+    pub fn classify_move(
+        &self,
+        positions: &RobotPositions,
+        robot: Robot,
+        direction: Direction,
+    ) -> MoveOutcome {
+        positions.try_move(self, robot, direction)
+    }
+
+    /// Computes, for every cell, an admissible lower bound on the number of slide-moves a robot
+    /// needs to reach `target_pos`.
+    ///
+    /// This is a reverse BFS/flood-fill seeded at `target_pos` with distance 0, run over the slide
+    /// graph rather than the grid graph: from a popped cell, every cell that can slide and stop at
+    /// or past it in one of the four directions is one move away, since another robot could in
+    /// principle be maneuvered into any of the intermediate cells to act as a blocker. Because real
+    /// blocking is a subset of this optimistic blocking, the result never overestimates the true
+    /// number of moves needed.
+    ///
+    /// This mirrors the board solvers already build per-round to prioritize search (see
+    /// `ricochet_solver`'s `LeastMovesBoard`), exposed here as a plain function of a `Board` and a
+    /// target cell for callers that only have those two things to work with.
+    ///
+    /// The returned `Vec` is indexed by `pos.column() * self.side_length() + pos.row()`. Cells that
+    /// can't reach the target even with optimal blockers are set to `u8::MAX`.
+    pub fn move_lower_bounds(&self, target_pos: Position) -> Vec<u8> {
+        let len = self.side_length() as usize;
+        let index = |pos: Position| pos.column() as usize * len + pos.row() as usize;
+
+        let mut bounds = vec![u8::MAX; len * len];
+        bounds[index(target_pos)] = 0;
+
+        let mut current_cells = Vec::with_capacity(256);
+        let mut next_cells = current_cells.clone();
+        current_cells.push(target_pos);
+
+        for move_n in 1usize.. {
+            for &pos in &current_cells {
+                for &dir in DIRECTIONS.iter() {
+                    let mut check_pos = pos;
+                    loop {
+                        if self.is_adjacent_to_wall(check_pos, dir) {
+                            break;
+                        }
+                        check_pos = check_pos.to_direction(dir, self.side_length());
+                        let slot = &mut bounds[index(check_pos)];
+                        if (move_n as u8) < *slot {
+                            *slot = move_n as u8;
+                            next_cells.push(check_pos);
+                        }
+                    }
+                }
+            }
+
+            if next_cells.is_empty() {
+                break;
+            }
+            current_cells.clear();
+            std::mem::swap(&mut current_cells, &mut next_cells);
+        }
+
+        bounds
+    }
+
+    /// Floods the robot-movement graph from `start`, returning every field a single robot starting
+    /// there could ever come to rest on.
+    ///
+    /// Unlike [`move_lower_bounds`](Board::move_lower_bounds), which credits a cell as reachable the
+    /// moment *some* slide could be blocked to stop there, this only follows a slide all the way to
+    /// where it actually stops on an empty board: a wall. Positions wrap around at the board's
+    /// edges, so this never returns if some slide meets no wall at all. A field not present in the
+    /// result can never hold a robot unassisted by another one standing on it first, which makes it
+    /// unreachable as a solo starting position or as a target nobody else can help reach.
+    pub fn reachable_fields(&self, start: Position) -> BTreeSet<Position> {
+        let mut visited = BTreeSet::new();
+        visited.insert(start);
+        let mut frontier = vec![start];
+
+        while let Some(pos) = frontier.pop() {
+            for &dir in DIRECTIONS.iter() {
+                let mut stop = pos;
+                while !self.is_adjacent_to_wall(stop, dir) {
+                    stop = stop.to_direction(dir, self.side_length());
+                }
+                if visited.insert(stop) {
+                    frontier.push(stop);
+                }
+            }
+        }
+
+        visited
+    }
 }
 
 impl ops::Index<Position> for Board {
@@ -363,16 +515,14 @@ impl Round {
     }
 
     /// Checks if the target has been reached.
+    ///
+    /// A robot has reached the target if it stands on [`target_position`](Round::target_position)
+    /// and [`Target::allows`](Target::allows) it there.
     pub fn target_reached(&self, positions: &RobotPositions) -> bool {
-        match self.target {
-            Target::Spiral => positions.contains_any_robot(self.target_position),
-            _ => positions.contains_colored_robot(
-                self.target
-                    .try_into()
-                    .expect("Failed to extract the robot corresponding to the target"),
-                self.target_position,
-            ),
-        }
+        ROBOTS
+            .iter()
+            .filter(|&&robot| self.target.allows(robot.into()))
+            .any(|&robot| positions.contains_colored_robot(robot, self.target_position))
     }
 }
 
@@ -430,23 +580,78 @@ impl Game {
         game
     }
 
-    /// Adds a quadrant to the board.
+    /// Creates a 16x16 game board from a list of quadrants, like [`from_quadrants`](Game::from_quadrants),
+    /// but validates each quadrant and the assembled set first.
+    ///
+    /// Runs [`BoardQuadrant::validate`](BoardQuadrant::validate) on every quadrant, turned back to
+    /// [`Orientation::UpperLeft`](Orientation::UpperLeft) first, then checks that
+    /// the set carries exactly one [`Target::Spiral`](Target::Spiral) between them, and surfaces the
+    /// first [`QuadrantError`](QuadrantError) found instead of silently assembling a board that
+    /// might not be solvable.
+    pub fn try_from_quadrants(quads: &[BoardQuadrant]) -> Result<Self, QuadrantError> {
+        for quad in quads {
+            // Rotating a quadrant can move its border walls to coordinate -1, so each one is
+            // checked in the upper left orientation its local coordinates are defined in.
+            let mut upright = quad.clone();
+            upright.rotate_to(Orientation::UpperLeft);
+            upright.validate()?;
+        }
+
+        let spiral_count = quads
+            .iter()
+            .flat_map(|quad| quad.targets())
+            .filter(|&&(_, target)| target == Target::Spiral)
+            .count();
+        if spiral_count != 1 {
+            return Err(QuadrantError::SpiralCountWrong(spiral_count));
+        }
+
+        Ok(Self::from_quadrants(quads))
+    }
+
+    /// Assembles an arbitrary `K x K` grid of quadrants into a `K * QUADRANT_SIDE_LENGTH`-wide
+    /// board, generalizing [`from_quadrants`](Game::from_quadrants)'s fixed four-quadrant, one per
+    /// color case to larger tiled boards such as the six-player "double" variant.
+    ///
+    /// `grid[row][col]` is placed `row` and `col` quadrants down and across respectively; unlike
+    /// `from_quadrants`, placement follows a quadrant's position in `grid` rather than its
+    /// [`Orientation`](quadrant::Orientation), which only distinguishes the four corners of a 2x2
+    /// board and so can't address a larger grid.
+    pub fn from_quadrant_grid<const K: usize>(grid: &[[BoardQuadrant; K]; K]) -> Self {
+        let side_length = K as PositionEncoding * quadrant::QUADRANT_SIDE_LENGTH;
+        let mut game = Game::new_enclosed(side_length);
+        for (row_idx, row) in grid.iter().enumerate() {
+            for (col_idx, quad) in row.iter().enumerate() {
+                let col_add = col_idx as PositionEncoding * quadrant::QUADRANT_SIDE_LENGTH;
+                let row_add = row_idx as PositionEncoding * quadrant::QUADRANT_SIDE_LENGTH;
+                game.add_quadrant_at(quad, col_add, row_add);
+            }
+        }
+        game
+    }
+
+    /// Adds a quadrant to the board, at the offset implied by its [`Orientation`](Orientation).
     ///
     /// Panics if `self.side_length() != 16`.
     fn add_quadrant(&mut self, quad: &BoardQuadrant) {
-        // get the needed offset
         let (col_add, row_add) = match quad.orientation() {
             Orientation::UpperLeft => (0, 0),
-            Orientation::UpperRight => (8, 0),
-            Orientation::BottomRight => (8, 8),
-            Orientation::BottomLeft => (0, 8),
+            Orientation::UpperRight => (quadrant::QUADRANT_SIDE_LENGTH, 0),
+            Orientation::BottomRight => (quadrant::QUADRANT_SIDE_LENGTH, quadrant::QUADRANT_SIDE_LENGTH),
+            Orientation::BottomLeft => (0, quadrant::QUADRANT_SIDE_LENGTH),
         };
+        self.add_quadrant_at(quad, col_add, row_add);
+    }
 
+    /// Adds a quadrant to the board at the explicit `(col_add, row_add)` field offset.
+    ///
+    /// Panics if `quad` doesn't fit on the board at that offset.
+    fn add_quadrant_at(&mut self, quad: &BoardQuadrant, col_add: PositionEncoding, row_add: PositionEncoding) {
         // set the walls
-        let walls: &mut Walls = &mut self.board.walls;
+        let walls: &mut Grid<Field> = &mut self.board.walls;
         for ((c, r), dir) in quad.walls() {
-            let c = (c + col_add) as usize;
-            let r = (r + row_add) as usize;
+            let c = (c + col_add as isize) as usize;
+            let r = (r + row_add as isize) as usize;
 
             match dir {
                 WallDirection::Down => walls[c][r].down = true,
@@ -456,8 +661,8 @@ impl Game {
 
         // set the targets
         for ((c, r), target) in quad.targets() {
-            let c = (c + col_add) as PositionEncoding;
-            let r = (r + row_add) as PositionEncoding;
+            let c = (c + col_add as isize) as PositionEncoding;
+            let r = (r + row_add as isize) as PositionEncoding;
             self.targets.insert(*target, Position::new(c, r));
         }
     }
@@ -483,7 +688,10 @@ impl fmt::Debug for Game {
 
 #[cfg(test)]
 mod tests {
-    use crate::{quadrant, Board, Direction, Game, Position, Robot, RobotPositions};
+    use crate::{
+        quadrant, Board, BoardError, Direction, Field, Game, Position, Robot, RobotPositions, Round,
+        Symbol, Target,
+    };
 
     fn create_board() -> (RobotPositions, Board) {
         let quadrants = quadrant::gen_quadrants()
@@ -507,6 +715,36 @@ mod tests {
         create_board();
     }
 
+    #[test]
+    fn try_new_rejects_ragged_walls() {
+        let walls = vec![vec![Field::default(); 2], vec![Field::default(); 3]];
+        assert_eq!(Board::try_new(walls), Err(BoardError::NotSquare));
+    }
+
+    #[test]
+    fn colored_target_only_allows_the_matching_robot() {
+        let target = Target::Red(Symbol::Circle);
+        assert!(target.allows(Robot::Red.into()));
+        assert!(!target.allows(Robot::Blue.into()));
+    }
+
+    #[test]
+    fn spiral_target_allows_any_robot() {
+        for &robot in &crate::ROBOTS {
+            assert!(Target::Spiral.allows(robot.into()));
+        }
+    }
+
+    #[test]
+    fn target_reached_respects_the_spiral_targets_any_robot_rule() {
+        let board = Board::new_empty(2).wall_enclosure();
+        let target_position = Position::new(0, 0);
+        let round = Round::new(board, Target::Spiral, target_position);
+
+        let positions = RobotPositions::from_tuples(&[(1, 1), (0, 0), (1, 0), (0, 1)]);
+        assert!(round.target_reached(&positions));
+    }
+
     #[test]
     fn move_right() {
         let (mut positions, board) = create_board();
@@ -538,4 +776,48 @@ mod tests {
         positions = positions.move_in_direction(&board, Robot::Green, Direction::Down);
         assert_eq!(positions[Robot::Green], Position::from((7, 6)));
     }
+
+    #[test]
+    fn classify_move_matches_try_move() {
+        let (positions, board) = create_board();
+
+        assert_eq!(
+            board.classify_move(&positions, Robot::Green, Direction::Right),
+            positions.try_move(&board, Robot::Green, Direction::Right)
+        );
+    }
+
+    #[test]
+    fn move_lower_bounds_on_empty_board() {
+        let board = Board::new_empty(2).wall_enclosure();
+        assert_eq!(board.move_lower_bounds(Position::new(0, 0)), vec![0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn move_lower_bounds_marks_unreachable_cells_as_max() {
+        let board = Board::new_empty(2)
+            .wall_enclosure()
+            .set_vertical_line(0, 0, 1)
+            .set_horizontal_line(0, 0, 1);
+        let bounds = board.move_lower_bounds(Position::new(1, 0));
+        assert_eq!(bounds[0], u8::MAX);
+    }
+
+    #[test]
+    fn reachable_fields_on_empty_board_reach_every_corner() {
+        let board = Board::new_empty(4).wall_enclosure();
+        let reachable = board.reachable_fields(Position::new(0, 0));
+        for corner in [(0, 0), (3, 0), (0, 3), (3, 3)] {
+            assert!(reachable.contains(&Position::from(corner)));
+        }
+    }
+
+    #[test]
+    fn reachable_fields_excludes_a_field_boxed_in_by_walls() {
+        let board = Board::new_empty(4)
+            .wall_enclosure()
+            .enclose_lengths(1, 1, 1, 1);
+        let reachable = board.reachable_fields(Position::new(0, 0));
+        assert!(!reachable.contains(&Position::new(1, 1)));
+    }
 }
@@ -22,7 +22,7 @@ pub struct Position {
 }
 
 /// Positions of all robots on the board.
-#[derive(Clone, Hash, PartialEq, Eq)]
+#[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RobotPositions {
     red: Position,
     blue: Position,
@@ -30,6 +30,40 @@ pub struct RobotPositions {
     yellow: Position,
 }
 
+/// The outcome of attempting to move a robot one slide of fields in a direction, as computed by
+/// [`RobotPositions::try_move`](RobotPositions::try_move).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveOutcome {
+    /// The robot slid to a new field.
+    Moved {
+        /// The field the robot started on.
+        from: Position,
+        /// The field the robot ends up on.
+        to: Position,
+        /// The number of fields the robot slid across.
+        distance: u8,
+        /// What stopped the robot from sliding any further.
+        stopped_by: StoppedBy,
+    },
+    /// The robot didn't move: the field right next to it in the attempted direction is either
+    /// behind a wall or occupied by another robot.
+    Blocked {
+        /// Whether a wall stands between the robot and the neighboring field.
+        by_wall: bool,
+        /// The robot occupying the neighboring field, if one does.
+        by_robot: Option<Robot>,
+    },
+}
+
+/// What stopped a robot from sliding any further, see [`MoveOutcome::Moved`](MoveOutcome::Moved).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoppedBy {
+    /// A wall stood past the field the robot ended up on.
+    Wall,
+    /// Another robot stood past the field the robot ended up on.
+    Robot(Robot),
+}
+
 impl Position {
     /// Number of bits used for the encoding.
     const BIT_COUNT: PositionEncoding = mem::size_of::<PositionEncoding>() as PositionEncoding * 8;
@@ -150,10 +184,45 @@ impl RobotPositions {
             Robot::Yellow => 3,
         };
         sorted.swap(0, robot_index);
-        sorted[1..3].sort();
+        sorted[1..4].sort();
         sorted
     }
 
+    /// Packs the robot positions into a single key, canonicalized the same way as
+    /// [`to_sorted_array`](Self::to_sorted_array): `main_robot`'s position occupies the highest 16
+    /// bits, followed by the other three robots' positions in sorted order. States that only differ
+    /// in which of the non-`main_robot` robots sits where collapse to the same key.
+    pub fn to_key(&self, main_robot: Robot) -> u64 {
+        self.to_sorted_array(main_robot)
+            .iter()
+            .fold(0u64, |key, pos| (key << 16) | pos.encoded_position as u64)
+    }
+
+    /// The inverse of [`to_key`](Self::to_key).
+    ///
+    /// Since `to_key` canonicalizes the non-`main_robot` robots by sorted position rather than by
+    /// color, the three of them are placed back in `ROBOTS` order (skipping `main_robot`'s own
+    /// color) rather than necessarily matching whatever `RobotPositions` originally produced the
+    /// key.
+    pub fn from_key(key: u64, main_robot: Robot) -> Self {
+        let decode = |shift: u32| Position {
+            encoded_position: (key >> shift) as PositionEncoding,
+        };
+        let other_positions = [decode(32), decode(16), decode(0)];
+        let mut others = other_positions.iter().copied();
+
+        let mut result = RobotPositions {
+            red: decode(48),
+            blue: decode(48),
+            green: decode(48),
+            yellow: decode(48),
+        };
+        for robot in ROBOTS.iter().copied().filter(|&robot| robot != main_robot) {
+            result.set_robot(robot, others.next().expect("exactly three non-main robots"));
+        }
+        result
+    }
+
     /// Sets the `robot` to `new_position`.
     fn set_robot(&mut self, robot: Robot, new_position: Position) {
         *match robot {
@@ -210,18 +279,78 @@ impl RobotPositions {
 
     /// Moves `robot` as far in the given `direction` as possible.
     pub fn move_in_direction(mut self, board: &Board, robot: Robot, direction: Direction) -> Self {
-        // start form the current position
-        let mut temp_pos = self[robot];
+        if let MoveOutcome::Moved { to, .. } = self.try_move(board, robot, direction) {
+            self.set_robot(robot, to);
+        }
+
+        self
+    }
+
+    /// Returns a copy of `self` with `robot` placed directly on `new_position`, bypassing the usual
+    /// wall/robot slide logic.
+    ///
+    /// Meant for callers that have already worked out where a slide stops by some other means (e.g.
+    /// a precomputed ray table) and only need to materialize the resulting state.
+    pub fn with_robot_at(&self, robot: Robot, new_position: Position) -> Self {
+        let mut result = self.clone();
+        result.set_robot(robot, new_position);
+        result
+    }
+
+    /// Places `robot` directly on `new_position` in place, bypassing the usual wall/robot slide
+    /// logic, like [`with_robot_at`](Self::with_robot_at) but mutating `self` instead of returning a
+    /// copy.
+    ///
+    /// Meant for search algorithms that descend and backtrack through the state space via
+    /// make/unmake rather than cloning a whole `RobotPositions` at every ply: call this once to
+    /// "make" a move (moving `robot` to where it stopped) and again with its previous position to
+    /// "unmake" it.
+    pub fn set_robot_in_place(&mut self, robot: Robot, new_position: Position) {
+        self.set_robot(robot, new_position);
+    }
+
+    /// Attempts to move `robot` as far in the given `direction` as possible, reporting why nothing
+    /// happened if the robot can't move at all.
+    pub fn try_move(&self, board: &Board, robot: Robot, direction: Direction) -> MoveOutcome {
+        let start = self[robot];
+
+        if !self.adjacent_reachable(board, start, direction) {
+            let next = start.to_direction(direction, board.side_length());
+            let by_robot = ROBOTS
+                .iter()
+                .copied()
+                .find(|&other| other != robot && self.contains_colored_robot(other, next));
+            return MoveOutcome::Blocked {
+                by_wall: board.is_adjacent_to_wall(start, direction),
+                by_robot,
+            };
+        }
 
-        // check if the next position is reachable from the temporary position
-        while self.adjacent_reachable(board, temp_pos, direction) {
-            temp_pos = temp_pos.to_direction(direction, board.side_length());
+        let mut pos = start;
+        let mut distance: u8 = 0;
+        while self.adjacent_reachable(board, pos, direction) {
+            pos = pos.to_direction(direction, board.side_length());
+            distance += 1;
         }
 
-        // set the robot to the last possible position
-        self.set_robot(robot, temp_pos);
+        let stopped_by = if board.is_adjacent_to_wall(pos, direction) {
+            StoppedBy::Wall
+        } else {
+            let next = pos.to_direction(direction, board.side_length());
+            let blocker = ROBOTS
+                .iter()
+                .copied()
+                .find(|&other| other != robot && self.contains_colored_robot(other, next))
+                .expect("the slide only stopped short of the wall because a robot is in the way");
+            StoppedBy::Robot(blocker)
+        };
 
-        self
+        MoveOutcome::Moved {
+            from: start,
+            to: pos,
+            distance,
+            stopped_by,
+        }
     }
 }
 
@@ -263,7 +392,7 @@ impl fmt::Display for RobotPositions {
 
 #[cfg(test)]
 mod tests {
-    use super::Position;
+    use super::{MoveOutcome, Position, StoppedBy};
     use crate::{Board, Direction, PositionEncoding, Robot, RobotPositions};
 
     #[test]
@@ -303,4 +432,82 @@ mod tests {
             &expected
         );
     }
+
+    #[test]
+    fn try_move_reports_moved() {
+        let board = Board::new_empty(16).wall_enclosure();
+        let pos = RobotPositions::from_tuples(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+
+        assert_eq!(
+            pos.try_move(&board, Robot::Yellow, Direction::Right),
+            MoveOutcome::Moved {
+                from: Position::new(1, 1),
+                to: Position::new(15, 1),
+                distance: 14,
+                stopped_by: StoppedBy::Wall,
+            }
+        );
+    }
+
+    #[test]
+    fn try_move_reports_what_stopped_a_slide_that_hit_another_robot() {
+        let board = Board::new_empty(16).wall_enclosure();
+        let pos = RobotPositions::from_tuples(&[(0, 0), (1, 0), (0, 1), (10, 1)]);
+
+        assert_eq!(
+            pos.try_move(&board, Robot::Yellow, Direction::Left),
+            MoveOutcome::Moved {
+                from: Position::new(10, 1),
+                to: Position::new(1, 1),
+                distance: 9,
+                stopped_by: StoppedBy::Robot(Robot::Green),
+            }
+        );
+    }
+
+    #[test]
+    fn try_move_reports_blocked_by_robot() {
+        let board = Board::new_empty(16).wall_enclosure();
+        let pos = RobotPositions::from_tuples(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+
+        assert_eq!(
+            pos.try_move(&board, Robot::Red, Direction::Right),
+            MoveOutcome::Blocked {
+                by_wall: false,
+                by_robot: Some(Robot::Blue),
+            }
+        );
+    }
+
+    #[test]
+    fn to_key_round_trips_through_from_key() {
+        // The non-main robots are already in ascending-position order here, matching the order
+        // `from_key` places them back in, so the round trip reproduces the exact original state.
+        let pos = RobotPositions::from_tuples(&[(3, 4), (0, 0), (5, 5), (10, 10)]);
+        let key = pos.to_key(Robot::Red);
+
+        assert_eq!(RobotPositions::from_key(key, Robot::Red), pos);
+    }
+
+    #[test]
+    fn to_key_collapses_states_differing_only_by_non_main_robot_identity() {
+        let pos = RobotPositions::from_tuples(&[(3, 4), (0, 1), (15, 2), (9, 9)]);
+        let swapped = RobotPositions::from_tuples(&[(3, 4), (15, 2), (0, 1), (9, 9)]);
+
+        assert_eq!(pos.to_key(Robot::Red), swapped.to_key(Robot::Red));
+    }
+
+    #[test]
+    fn try_move_reports_blocked_by_wall() {
+        let board = Board::new_empty(16).wall_enclosure();
+        let pos = RobotPositions::from_tuples(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+
+        assert_eq!(
+            pos.try_move(&board, Robot::Red, Direction::Up),
+            MoveOutcome::Blocked {
+                by_wall: true,
+                by_robot: None,
+            }
+        );
+    }
 }
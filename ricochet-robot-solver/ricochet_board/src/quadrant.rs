@@ -3,16 +3,27 @@
 //! These quadrants are the same as the ones used to build the physical board.
 
 use draw_a_box::{find_character, Weight};
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::fmt;
 
-use crate::draw::{FIELD_DRAW_HEIGHT, FIELD_DRAW_WIDTH};
-use crate::{Field, Game, PositionEncoding, Round, Symbol, Target, TARGETS};
+use crate::draw::{AsciiGrid, ParseError, FIELD_DRAW_HEIGHT, FIELD_DRAW_WIDTH};
+use crate::{Direction, Field, Game, PositionEncoding, Round, Symbol, Target, TARGETS};
 
 /// The side length of the standard physical board.
 pub const STANDARD_BOARD_SIZE: PositionEncoding = 16;
 
-/// The side length of a quadrant.
-const QUADRANT_SIZE: PositionEncoding = STANDARD_BOARD_SIZE / 2 + 1;
+/// The side length of a single quadrant piece, i.e. the number of fields it contributes to an
+/// assembled board along one edge.
+///
+/// [`Game::from_quadrant_grid`](crate::Game::from_quadrant_grid) places a `K`-wide grid of
+/// quadrants on a `K * QUADRANT_SIDE_LENGTH`-wide board; [`from_quadrants`](crate::Game::from_quadrants)
+/// is the `K = 2` special case, placing its four quadrants `QUADRANT_SIDE_LENGTH` fields apart.
+pub(crate) const QUADRANT_SIDE_LENGTH: PositionEncoding = STANDARD_BOARD_SIZE / 2;
+
+/// The side length of a quadrant's own coordinate grid, one field larger than
+/// [`QUADRANT_SIDE_LENGTH`] to reserve room for the shared border [`Display`](fmt::Display) draws.
+const QUADRANT_SIZE: PositionEncoding = QUADRANT_SIDE_LENGTH + 1;
 
 /// All possible orientations of a quadrant.
 pub const ORIENTATIONS: [Orientation; 4] = [
@@ -122,6 +133,33 @@ impl WallDirection {
     }
 }
 
+/// The quadrant-local cell that ends up part of the board's center 2x2 block once four quadrants
+/// are assembled, see [`Game::new_enclosed`](crate::Game::new_enclosed). No target belongs here: the
+/// center block is already walled off on every side regardless of which quadrant occupies it.
+const CENTER_CELL: (isize, isize) = (QUADRANT_SIZE as isize - 2, QUADRANT_SIZE as isize - 2);
+
+/// Error returned by [`BoardQuadrant::validate`](BoardQuadrant::validate) and
+/// [`Game::try_from_quadrants`](crate::Game::try_from_quadrants).
+///
+/// Modeled on [`MoveOutcome`](crate::MoveOutcome)'s reason codes: each variant names a concrete way
+/// a quadrant, or the set of quadrants making up an assembled board, can be malformed, rather than
+/// collapsing every failure into one "invalid" case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuadrantError {
+    /// A wall sits outside `0..QUADRANT_SIZE` on at least one axis.
+    WallOffBoard((isize, isize)),
+    /// A target sits outside `0..QUADRANT_SIZE` on at least one axis.
+    TargetOffBoard((isize, isize)),
+    /// A target sits on [`CENTER_CELL`], the cell shared with the board's center 2x2 block.
+    TargetOnCenter((isize, isize)),
+    /// Two targets share both a color and a symbol.
+    DuplicateTargetColorSymbol(Target),
+    /// A target has no wall on any of its four sides, so no robot could ever come to rest on it.
+    TargetWithoutAdjacentWall((isize, isize)),
+    /// An assembled board didn't contain exactly one [`Target::Spiral`](crate::Target::Spiral).
+    SpiralCountWrong(usize),
+}
+
 /// A quadrant representing a quarter of the ricochet board.
 ///
 /// The physical board is built from four 8x8 pieces. Each of these pieces is assigned a color and
@@ -169,11 +207,11 @@ impl BoardQuadrant {
             .iter()
             .map(|&((c, r), dir)| match dir {
                 WallDirection::Right => (
-                    ((STANDARD_BOARD_SIZE / 2) as isize - r - 1, c),
+                    (QUADRANT_SIDE_LENGTH as isize - r - 1, c),
                     dir.rotate(),
                 ),
                 WallDirection::Down => (
-                    ((STANDARD_BOARD_SIZE / 2 - 1) as isize - r - 1, c),
+                    (QUADRANT_SIDE_LENGTH as isize - 1 - r - 1, c),
                     dir.rotate(),
                 ),
             })
@@ -182,7 +220,7 @@ impl BoardQuadrant {
         self.targets = self
             .targets
             .iter()
-            .map(|&((c, r), t)| (((STANDARD_BOARD_SIZE / 2) as isize - r - 1, c), t))
+            .map(|&((c, r), t)| ((QUADRANT_SIDE_LENGTH as isize - r - 1, c), t))
             .collect();
     }
 
@@ -193,6 +231,53 @@ impl BoardQuadrant {
         }
     }
 
+    /// Parses a quadrant of `color`, in the upper left orientation, back from the grid produced by
+    /// its own [`Display`](fmt::Display) implementation.
+    ///
+    /// Walls and target glyphs are read with the same box-drawing and symbol/color conventions
+    /// [`Board::from_ascii`](crate::Board::from_ascii) and [`ascii`](crate::ascii) use, so
+    /// `BoardQuadrant::from_ascii(&quad.to_string(), quad.color())` round-trips `quad` for any
+    /// upper-left-oriented `quad`. `color` is taken separately since nothing in the rendered grid
+    /// identifies which of the four quadrant colors it belongs to.
+    pub fn from_ascii(ascii: &str, color: QuadColor) -> Result<Self, ParseError> {
+        let grid = AsciiGrid::parse(ascii)?;
+        let side_length = grid.side_length();
+        if side_length != (QUADRANT_SIZE - 1) as usize {
+            return Err(ParseError::InvalidDimensions);
+        }
+
+        let walls_grid = grid.walls();
+        let mut walls = Vec::new();
+        let mut targets = Vec::new();
+        for col in 0..side_length {
+            for row in 0..side_length {
+                let pos = (col as isize, row as isize);
+                let field = walls_grid[col][row];
+                if field.down {
+                    walls.push((pos, WallDirection::Down));
+                }
+                if field.right {
+                    walls.push((pos, WallDirection::Right));
+                }
+
+                let target = crate::ascii::parse_target(
+                    grid.left_glyph(col, row),
+                    grid.right_glyph(col, row),
+                )?;
+                if let Some(target) = target {
+                    targets.push((pos, target));
+                }
+            }
+        }
+
+        Ok(BoardQuadrant {
+            orientation: Orientation::UpperLeft,
+            color,
+            walls,
+            targets,
+        })
+    }
+
     /// Creates a default quadrant of `color` in the upper left with no walls or targets.
     fn default_quadrant(color: QuadColor) -> Self {
         BoardQuadrant {
@@ -216,6 +301,66 @@ impl BoardQuadrant {
         self.targets.push((pos, target));
         self
     }
+
+    /// Checks `self` for the invariants a well-formed quadrant must hold, returning the first
+    /// violation found.
+    ///
+    /// Checks, in order: every wall and target cell lies within `0..QUADRANT_SIZE`, no target sits
+    /// on [`CENTER_CELL`], no two targets share a color and symbol, and every target has at least
+    /// one adjoining wall (the physical board's L-wall convention, see [`corner_wall_pair`]).
+    /// [`Game::try_from_quadrants`](crate::Game::try_from_quadrants) also checks that an assembled
+    /// set of quadrants carries exactly one spiral target, which isn't a property of a single
+    /// quadrant and so isn't checked here.
+    pub fn validate(&self) -> Result<(), QuadrantError> {
+        for &(pos, _) in &self.walls {
+            if !Self::in_bounds(pos) {
+                return Err(QuadrantError::WallOffBoard(pos));
+            }
+        }
+
+        for &(pos, _) in &self.targets {
+            if !Self::in_bounds(pos) {
+                return Err(QuadrantError::TargetOffBoard(pos));
+            }
+            if pos == CENTER_CELL {
+                return Err(QuadrantError::TargetOnCenter(pos));
+            }
+        }
+
+        let mut seen_targets = Vec::with_capacity(self.targets.len());
+        for &(_, target) in &self.targets {
+            if seen_targets.contains(&target) {
+                return Err(QuadrantError::DuplicateTargetColorSymbol(target));
+            }
+            seen_targets.push(target);
+        }
+
+        for &(pos, _) in &self.targets {
+            if !self.has_adjacent_wall(pos) {
+                return Err(QuadrantError::TargetWithoutAdjacentWall(pos));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if `pos` lies within `0..QUADRANT_SIZE` on both axes.
+    fn in_bounds(pos: (isize, isize)) -> bool {
+        let (c, r) = pos;
+        (0..QUADRANT_SIZE as isize).contains(&c) && (0..QUADRANT_SIZE as isize).contains(&r)
+    }
+
+    /// Returns `true` if a wall adjoins any of `pos`'s four sides.
+    ///
+    /// Mirrors [`corner_wall_pair`]'s convention: a wall above or to the left of `pos` is stored as
+    /// the neighboring cell's `Down`/`Right` entry rather than one of `pos`'s own.
+    fn has_adjacent_wall(&self, pos: (isize, isize)) -> bool {
+        let (c, r) = pos;
+        self.walls.contains(&(pos, WallDirection::Down))
+            || self.walls.contains(&(pos, WallDirection::Right))
+            || self.walls.contains(&((c, r - 1), WallDirection::Down))
+            || self.walls.contains(&((c - 1, r), WallDirection::Right))
+    }
 }
 
 impl fmt::Display for BoardQuadrant {
@@ -231,7 +376,16 @@ impl fmt::Display for BoardQuadrant {
             }
         }
 
+        let print = crate::Grid::from_columns(print);
         let (mut canvas, mut weights) = crate::draw::create_board_string_vec(&print);
+
+        for ((c, r), target) in &self.targets {
+            let base_col = (c + 1) as usize * FIELD_DRAW_WIDTH;
+            let row = (r + 1) as usize * FIELD_DRAW_HEIGHT + 1;
+            canvas[base_col + 1][row] = crate::draw::target_symbol_glyph(*target);
+            canvas[base_col + 3][row] = crate::draw::target_color_glyph(*target);
+        }
+
         let mut output = String::new();
 
         // Remove the first column and first row and smoothen the now outer boarder.
@@ -514,3 +668,359 @@ pub fn gen_quadrants() -> Vec<BoardQuadrant> {
             .set_target((7, 5), Target::Spiral),
     ]
 }
+
+/// Rows and columns [`gen_random_quadrant`] draws its target cells from: the 5x5 block strictly
+/// inside the quadrant's outer edge, with just enough room to pick up to 5 cells that share no row
+/// or column and excludes `(6, 6)`, the corner of the 2x2 block nearest the board's center.
+const INTERIOR_LINES: [isize; 5] = [2, 3, 4, 5, 6];
+
+/// Synthesizes a new `color` quadrant with 4 or 5 procedurally placed targets, instead of picking
+/// one of the 12 fixed quadrants [`gen_quadrants`] returns.
+///
+/// Mirrors the physical board's own convention: every interior target sits in the crook of an
+/// L-shaped wall pair, so each target cell gets a random [`Direction`] deciding which of its four
+/// corners the two walls open into (see [`corner_wall_pair`]). Candidate cells are drawn from
+/// [`INTERIOR_LINES`] with distinct rows and columns, so no two targets' walls can land on the same
+/// edge and merge, and resampled whenever the chosen walls would box some cell in on all four
+/// sides. The result is always in [`Orientation::UpperLeft`], so
+/// [`rotate_to`](BoardQuadrant::rotate_to) works on it unchanged.
+pub fn gen_random_quadrant(color: QuadColor, rng: &mut impl Rng) -> BoardQuadrant {
+    loop {
+        let count = rng.gen_range(4..=5);
+
+        let mut cols = INTERIOR_LINES;
+        let mut rows = INTERIOR_LINES;
+        cols.shuffle(rng);
+        rows.shuffle(rng);
+        let cells: Vec<(isize, isize)> = cols
+            .iter()
+            .copied()
+            .zip(rows.iter().copied())
+            .take(count)
+            .filter(|&pos| pos != (6, 6))
+            .collect();
+        if cells.len() != count {
+            continue;
+        }
+
+        let targets: Vec<Target> = TARGETS.choose_multiple(rng, count).copied().collect();
+        let walls: Vec<((isize, isize), WallDirection)> = cells
+            .iter()
+            .flat_map(|&cell| corner_wall_pair(cell, *crate::DIRECTIONS.choose(rng).unwrap()))
+            .collect();
+
+        if traps_a_cell(&walls) {
+            continue;
+        }
+
+        return BoardQuadrant {
+            orientation: Orientation::UpperLeft,
+            color,
+            walls,
+            targets: cells.into_iter().zip(targets).collect(),
+        };
+    }
+}
+
+/// The two wall segments forming the L-shaped corner of `cell` that `orientation` opens into,
+/// expressed in the same `(col, row)`/[`WallDirection`] convention [`BoardQuadrant::set_walls`]
+/// does.
+///
+/// Mirrors `generator::place_corner_wall`'s four corner cases, without a [`Board`](crate::Board) to
+/// mutate.
+fn corner_wall_pair(
+    cell: (isize, isize),
+    orientation: Direction,
+) -> [((isize, isize), WallDirection); 2] {
+    let (c, r) = cell;
+    match orientation {
+        Direction::Up => [((c, r - 1), WallDirection::Down), (cell, WallDirection::Right)],
+        Direction::Right => [(cell, WallDirection::Right), (cell, WallDirection::Down)],
+        Direction::Down => [(cell, WallDirection::Down), ((c - 1, r), WallDirection::Right)],
+        Direction::Left => [
+            ((c - 1, r), WallDirection::Right),
+            ((c, r - 1), WallDirection::Down),
+        ],
+    }
+}
+
+/// Returns `true` if `walls` box some field in on all four sides, which would trap any robot that
+/// ever lands on it.
+fn traps_a_cell(walls: &[((isize, isize), WallDirection)]) -> bool {
+    let has = |pos: (isize, isize), dir: WallDirection| walls.contains(&(pos, dir));
+
+    (0..QUADRANT_SIZE as isize - 1).any(|c| {
+        (0..QUADRANT_SIZE as isize - 1).any(|r| {
+            has((c, r), WallDirection::Right)
+                && has((c - 1, r), WallDirection::Right)
+                && has((c, r), WallDirection::Down)
+                && has((c, r - 1), WallDirection::Down)
+        })
+    })
+}
+
+/// Assembles a full [`Game`] from four freshly-synthesized quadrants, one per color, in random
+/// orientations — the procedural counterpart to [`game_from_seed`], which only shuffles the 12
+/// fixed quadrants [`gen_quadrants`] returns.
+pub fn random_game(rng: &mut impl Rng) -> Game {
+    let colors = [
+        QuadColor::Red,
+        QuadColor::Blue,
+        QuadColor::Green,
+        QuadColor::Yellow,
+    ];
+    let mut orientations = ORIENTATIONS;
+    orientations.shuffle(rng);
+
+    let quads: Vec<BoardQuadrant> = colors
+        .iter()
+        .copied()
+        .zip(orientations.iter().copied())
+        .map(|(color, orientation)| {
+            let mut quad = gen_random_quadrant(color, rng);
+            quad.rotate_to(orientation);
+            quad
+        })
+        .collect();
+
+    Game::from_quadrants(&quads)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{
+        gen_quadrants, gen_random_quadrant, random_game, BoardQuadrant, Orientation, QuadColor,
+        QuadrantError, WallDirection, CENTER_CELL, ORIENTATIONS, QUADRANT_SIDE_LENGTH, QUADRANT_SIZE,
+        STANDARD_BOARD_SIZE,
+    };
+    use crate::{Game, Symbol, Target};
+
+    /// A wall, with its direction reduced to a `bool` so two quadrants' wall lists can be compared
+    /// regardless of push order.
+    fn canonical_walls(quad: &BoardQuadrant) -> BTreeSet<((isize, isize), bool)> {
+        quad.walls()
+            .iter()
+            .map(|&(pos, dir)| (pos, dir == WallDirection::Right))
+            .collect()
+    }
+
+    fn canonical_targets(quad: &BoardQuadrant) -> BTreeSet<((isize, isize), Target)> {
+        quad.targets().iter().cloned().collect()
+    }
+
+    #[test]
+    fn from_ascii_round_trips_through_display_for_every_standard_quadrant() {
+        for quad in gen_quadrants() {
+            let parsed = BoardQuadrant::from_ascii(&quad.to_string(), quad.color())
+                .expect("well-formed ascii");
+
+            assert_eq!(parsed.color(), quad.color());
+            assert_eq!(parsed.orientation(), Orientation::UpperLeft);
+            assert_eq!(canonical_walls(&parsed), canonical_walls(&quad));
+            assert_eq!(canonical_targets(&parsed), canonical_targets(&quad));
+        }
+    }
+
+    #[test]
+    fn from_ascii_rejects_the_wrong_side_length() {
+        let board = crate::Board::new_empty(4).wall_enclosure();
+        let ascii = crate::draw_board(board.get_walls());
+
+        assert_eq!(
+            BoardQuadrant::from_ascii(&ascii, gen_quadrants()[0].color()),
+            Err(crate::ParseError::InvalidDimensions)
+        );
+    }
+
+    #[test]
+    fn gen_random_quadrant_places_4_or_5_targets_on_distinct_rows_and_columns() {
+        for seed in 0..50 {
+            let mut rng = rand_pcg::Pcg64Mcg::new(seed);
+            let quad = gen_random_quadrant(QuadColor::Red, &mut rng);
+
+            assert_eq!(quad.color(), QuadColor::Red);
+            assert_eq!(quad.orientation(), Orientation::UpperLeft);
+            assert!((4..=5).contains(&quad.targets().len()));
+
+            let cols: BTreeSet<isize> = quad.targets().iter().map(|&((c, _), _)| c).collect();
+            let rows: BTreeSet<isize> = quad.targets().iter().map(|&((_, r), _)| r).collect();
+            assert_eq!(cols.len(), quad.targets().len());
+            assert_eq!(rows.len(), quad.targets().len());
+
+            let targets: BTreeSet<Target> = quad.targets().iter().map(|&(_, t)| t).collect();
+            assert_eq!(targets.len(), quad.targets().len());
+        }
+    }
+
+    #[test]
+    fn gen_random_quadrant_never_traps_a_cell() {
+        for seed in 0..50 {
+            let mut rng = rand_pcg::Pcg64Mcg::new(seed);
+            let quad = gen_random_quadrant(QuadColor::Blue, &mut rng);
+            assert!(!super::traps_a_cell(quad.walls()));
+        }
+    }
+
+    #[test]
+    fn random_game_assembles_a_standard_sized_board() {
+        let mut rng = rand_pcg::Pcg64Mcg::new(7);
+        let game = random_game(&mut rng);
+
+        assert_eq!(game.board().side_length(), STANDARD_BOARD_SIZE);
+        assert!(!game.targets().is_empty());
+    }
+
+    #[test]
+    fn validate_accepts_every_standard_quadrant() {
+        for quad in gen_quadrants() {
+            assert_eq!(quad.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn validate_accepts_every_procedurally_generated_quadrant() {
+        for seed in 0..50 {
+            let mut rng = rand_pcg::Pcg64Mcg::new(seed);
+            let quad = gen_random_quadrant(QuadColor::Green, &mut rng);
+            assert_eq!(quad.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn validate_rejects_a_wall_off_the_quadrant() {
+        let quad = gen_quadrants()[0]
+            .clone()
+            .set_walls(WallDirection::Down, vec![(QUADRANT_SIZE as isize, 0)]);
+        assert_eq!(
+            quad.validate(),
+            Err(QuadrantError::WallOffBoard((QUADRANT_SIZE as isize, 0)))
+        );
+    }
+
+    #[test]
+    fn validate_rejects_a_target_off_the_quadrant() {
+        let quad = gen_quadrants()[0]
+            .clone()
+            .set_target((-1, 0), Target::Spiral);
+        assert_eq!(
+            quad.validate(),
+            Err(QuadrantError::TargetOffBoard((-1, 0)))
+        );
+    }
+
+    #[test]
+    fn validate_rejects_a_target_on_the_center_cell() {
+        let quad = gen_quadrants()[0].clone().set_target(CENTER_CELL, Target::Spiral);
+        assert_eq!(quad.validate(), Err(QuadrantError::TargetOnCenter(CENTER_CELL)));
+    }
+
+    #[test]
+    fn validate_rejects_two_targets_with_the_same_color_and_symbol() {
+        let quad = gen_quadrants()[0]
+            .clone()
+            .set_walls(WallDirection::Down, vec![(2, 2)])
+            .set_target((2, 2), Target::Red(Symbol::Triangle));
+        assert_eq!(
+            quad.validate(),
+            Err(QuadrantError::DuplicateTargetColorSymbol(Target::Red(
+                Symbol::Triangle
+            )))
+        );
+    }
+
+    #[test]
+    fn validate_rejects_a_target_without_an_adjacent_wall() {
+        let quad = gen_quadrants()[0]
+            .clone()
+            .set_target((2, 2), Target::Spiral);
+        assert_eq!(
+            quad.validate(),
+            Err(QuadrantError::TargetWithoutAdjacentWall((2, 2)))
+        );
+    }
+
+    #[test]
+    fn try_from_quadrants_accepts_a_well_formed_standard_board() {
+        let quadrants: Vec<BoardQuadrant> = gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(ORIENTATIONS[i]);
+                quad
+            })
+            .collect();
+
+        assert!(Game::try_from_quadrants(&quadrants).is_ok());
+    }
+
+    #[test]
+    fn try_from_quadrants_rejects_a_board_without_exactly_one_spiral() {
+        let quadrants: Vec<BoardQuadrant> = gen_quadrants()
+            .iter()
+            .filter(|quad| quad.color() != QuadColor::Yellow)
+            .cloned()
+            .take(4)
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(ORIENTATIONS[i]);
+                quad
+            })
+            .collect();
+
+        assert_eq!(
+            Game::try_from_quadrants(&quadrants),
+            Err(QuadrantError::SpiralCountWrong(0))
+        );
+    }
+
+    #[test]
+    fn from_quadrant_grid_matches_from_quadrants_for_the_standard_2x2_case() {
+        let quads: Vec<BoardQuadrant> = gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(ORIENTATIONS[i]);
+                quad
+            })
+            .collect();
+
+        // ORIENTATIONS is [UpperLeft, UpperRight, BottomRight, BottomLeft], so `grid[row][col]`
+        // puts quads[0]/[1] across the top row and quads[3]/[2] across the bottom.
+        let grid = [
+            [quads[0].clone(), quads[1].clone()],
+            [quads[3].clone(), quads[2].clone()],
+        ];
+
+        let from_grid = Game::from_quadrant_grid(&grid);
+        let from_flat = Game::from_quadrants(&quads);
+
+        assert_eq!(from_grid.board().side_length(), STANDARD_BOARD_SIZE);
+        assert_eq!(from_grid.board(), from_flat.board());
+        assert_eq!(from_grid.targets(), from_flat.targets());
+    }
+
+    #[test]
+    fn from_quadrant_grid_places_a_single_quadrant_unoffset() {
+        let quad = gen_quadrants()[0].clone();
+        let grid = [[quad.clone()]];
+
+        let game = Game::from_quadrant_grid(&grid);
+
+        assert_eq!(game.board().side_length(), QUADRANT_SIDE_LENGTH);
+        for &((c, r), target) in quad.targets() {
+            assert_eq!(
+                game.get_target_position(&target),
+                Some(crate::Position::new(
+                    c as crate::PositionEncoding,
+                    r as crate::PositionEncoding
+                ))
+            );
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! A simple, self-contained planner on top of [`Round`](Round), independent of the more
+//! sophisticated solvers in `ricochet_solver`.
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+
+use crate::{Direction, Position, Robot, RobotPositions, Round, ROBOTS};
+
+/// Maps a visited `RobotPositions` to the move that reached it and the `RobotPositions` it was
+/// reached from, or `None` for the starting position.
+type Predecessors = HashMap<RobotPositions, Option<(RobotPositions, (Robot, Direction))>>;
+
+impl Round {
+    /// Finds a shortest sequence of moves from `start` to this round's target, or `None` if the
+    /// target can't be reached.
+    ///
+    /// Performs a breadth-first search over game states: a state is a full [`RobotPositions`],
+    /// successors are generated by [`reachable_positions`](RobotPositions::reachable_positions),
+    /// and a visited map keyed on `RobotPositions` prevents re-expanding the same state twice. For
+    /// boards deep enough that the visited map grows too large, see
+    /// [`solve_ida`](Round::solve_ida) instead.
+    pub fn solve(&self, start: RobotPositions) -> Option<Vec<(Robot, Direction)>> {
+        if self.target_reached(&start) {
+            return Some(Vec::new());
+        }
+
+        let mut predecessors = Predecessors::new();
+        predecessors.insert(start.clone(), None);
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+
+        while let Some(pos) = queue.pop_front() {
+            for (next, movement) in pos.reachable_positions(&self.board) {
+                if predecessors.contains_key(&next) {
+                    continue;
+                }
+
+                if self.target_reached(&next) {
+                    predecessors.insert(next.clone(), Some((pos, movement)));
+                    return Some(reconstruct_path(&predecessors, next));
+                }
+
+                predecessors.insert(next.clone(), Some((pos.clone(), movement)));
+                queue.push_back(next);
+            }
+        }
+
+        None
+    }
+
+    /// Finds a sequence of moves from `start` to this round's target using iterative deepening, or
+    /// `None` if the target can't be reached.
+    ///
+    /// Unlike [`solve`](Round::solve), this doesn't keep every visited state in memory: it performs
+    /// repeated depth-first searches with a growing depth bound, pruning branches with
+    /// [`move_heuristic`](Round::move_heuristic), an estimate of the moves still needed. That
+    /// estimate ignores other robots and can be one move too high, so the sequence found is not
+    /// always a shortest one.
+    pub fn solve_ida(&self, start: RobotPositions) -> Option<Vec<(Robot, Direction)>> {
+        if self.target_reached(&start) {
+            return Some(Vec::new());
+        }
+
+        // No shortest path can need more moves than this; used to recognize an unreachable target
+        // instead of deepening forever. Mirrors `LeastMovesBoard::is_unsolvable` in `ricochet_solver`.
+        let max_possible_depth = (self.board.side_length() as usize).pow(2);
+
+        let mut path = Vec::new();
+        for max_depth in self.move_heuristic(&start)..=max_possible_depth {
+            if self.solve_ida_limited(start.clone(), max_depth, &mut path) {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// Performs a depth-limited DFS from `pos`, pushing the moves taken onto `path`. Returns `true`
+    /// once the target has been reached, leaving `path` holding the moves to get there; otherwise
+    /// restores `path` to its original contents before returning `false`.
+    fn solve_ida_limited(
+        &self,
+        pos: RobotPositions,
+        max_depth: usize,
+        path: &mut Vec<(Robot, Direction)>,
+    ) -> bool {
+        if self.target_reached(&pos) {
+            return true;
+        }
+        if max_depth == 0 {
+            return false;
+        }
+
+        for (next, movement) in pos.reachable_positions(&self.board) {
+            if max_depth - 1 < self.move_heuristic(&next) {
+                continue;
+            }
+
+            path.push(movement);
+            if self.solve_ida_limited(next, max_depth - 1, path) {
+                return true;
+            }
+            path.pop();
+        }
+
+        false
+    }
+
+    /// An estimate of the moves needed to reach this round's target from `positions`.
+    ///
+    /// Returns `0` if the target robot is already on [`target_position`](Round::target_position),
+    /// `1` if it shares the target's row or column and walls alone would stop it exactly on the
+    /// target, or `2` otherwise. Other robots are not taken into account: where a robot just beyond
+    /// the target would stop the target robot on it, this returns `2` although one move suffices.
+    fn move_heuristic(&self, positions: &RobotPositions) -> usize {
+        match Robot::try_from(self.target) {
+            Ok(robot) => self.single_robot_heuristic(positions[robot]),
+            Err(_) => ROBOTS
+                .iter()
+                .map(|&robot| self.single_robot_heuristic(positions[robot]))
+                .min()
+                .expect("ROBOTS is non-empty"),
+        }
+    }
+
+    /// The [`move_heuristic`](Round::move_heuristic) contribution of a single robot sitting on
+    /// `pos`.
+    fn single_robot_heuristic(&self, pos: Position) -> usize {
+        if pos == self.target_position {
+            return 0;
+        }
+
+        let lined_up = pos.column() == self.target_position.column()
+            || pos.row() == self.target_position.row();
+        if lined_up && self.stops_on_target(pos) {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Checks whether a robot moving from `pos` straight towards the target, stopping only for
+    /// walls, would come to rest exactly on the target. Positions wrap around at the board's
+    /// edges, so this never returns if the line through `pos` and the target has no wall.
+    ///
+    /// Ignores other robots: one between `pos` and the target would stop the move earlier, and one
+    /// just beyond the target would stop it on the target, which this does not see.
+    fn stops_on_target(&self, pos: Position) -> bool {
+        let target = self.target_position;
+        let direction = if pos.column() == target.column() {
+            if target.row() > pos.row() {
+                Direction::Down
+            } else {
+                Direction::Up
+            }
+        } else if pos.row() == target.row() {
+            if target.column() > pos.column() {
+                Direction::Right
+            } else {
+                Direction::Left
+            }
+        } else {
+            return false;
+        };
+
+        let mut current = pos;
+        while !self.board.is_adjacent_to_wall(current, direction) {
+            current = current.to_direction(direction, self.board.side_length());
+        }
+        current == target
+    }
+}
+
+/// Walks `predecessors` backwards from `end` to the start, collecting the moves taken along the
+/// way in the order they were made.
+fn reconstruct_path(
+    predecessors: &Predecessors,
+    mut current: RobotPositions,
+) -> Vec<(Robot, Direction)> {
+    let mut movements = Vec::new();
+    while let Some((previous, movement)) = predecessors[&current].clone() {
+        movements.push(movement);
+        current = previous;
+    }
+    movements.reverse();
+    movements
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{quadrant, Direction, Game, Robot, RobotPositions, Round, Symbol, Target};
+
+    fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    #[test]
+    fn solve_returns_no_moves_when_already_on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        assert_eq!(round.solve(start), Some(Vec::new()));
+    }
+
+    #[test]
+    fn solve_and_solve_ida_agree_on_path_length() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let bfs = round.solve(pos.clone()).expect("round is solvable");
+        let ida = round.solve_ida(pos).expect("round is solvable");
+
+        assert_eq!(bfs.len(), ida.len());
+    }
+
+    #[test]
+    fn solve_reaches_the_target() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let movements = round.solve(pos.clone()).expect("round is solvable");
+        let final_pos = movements.iter().fold(pos, |acc, &(robot, dir)| {
+            acc.move_in_direction(round.board(), robot, dir)
+        });
+
+        assert!(round.target_reached(&final_pos));
+    }
+
+    #[test]
+    fn move_heuristic_is_zero_on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let on_target =
+            RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        assert_eq!(round.move_heuristic(&on_target), 0);
+    }
+
+    #[test]
+    fn solve_returns_none_when_unreachable() {
+        let board = crate::Board::new_empty(2)
+            .wall_enclosure()
+            .set_vertical_line(0, 0, 1)
+            .set_horizontal_line(0, 0, 1);
+        let target_position = crate::Position::new(1, 0);
+        let round = Round::new(board, Target::Spiral, target_position);
+        let pos = RobotPositions::from_tuples(&[(0, 0), (0, 0), (0, 0), (0, 0)]);
+
+        assert_eq!(round.solve(pos.clone()), None);
+        assert_eq!(round.solve_ida(pos), None);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::draw::{robot_color, target_color, target_symbol_glyph, Color};
+use crate::{Direction, Robot, RobotPositions, Round, ROBOTS};
+
+/// Pixel size of one board field in the rendered SVG.
+///
+/// Independent of [`FIELD_DRAW_WIDTH`](crate::FIELD_DRAW_WIDTH)/
+/// [`FIELD_DRAW_HEIGHT`](crate::FIELD_DRAW_HEIGHT): those describe a 5x2 monospace character cell
+/// for the ascii renderer, which isn't square, while every field here is drawn as a uniform
+/// `SVG_CELL_SIZE`-pixel square.
+const SVG_CELL_SIZE: u32 = 40;
+
+/// Wall line thickness, in pixels.
+const WALL_STROKE: u32 = 4;
+
+/// Draws `round`'s board with `positions`' robots and the target overlaid, as a single static SVG
+/// image.
+///
+/// Walls come straight from [`Board::get_walls`](crate::Board::get_walls), the target cell is
+/// filled with its color and marked with its [`Symbol`](crate::Symbol), and every [`Robot`] is
+/// drawn as a circle of its color at its position in `positions`.
+pub fn draw_svg(round: &Round, positions: &RobotPositions) -> String {
+    let side_length = round.board().side_length() as u32;
+    let mut svg = String::new();
+    open_svg(&mut svg, side_length);
+    push_walls(&mut svg, round, side_length);
+    push_target(&mut svg, round);
+    push_robots(&mut svg, positions);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Draws `round` as a self-contained HTML page animating `movements` played out from `positions`.
+///
+/// The board and target are drawn once; each step of `movements` gets its own SVG `<g>` holding
+/// that frame's robot positions, and a small inline `<script>` steps through them one at a time so
+/// a solution can be watched play out in a browser, rather than only inspected as a static image.
+pub fn draw_html(round: &Round, positions: &RobotPositions, movements: &[(Robot, Direction)]) -> String {
+    let mut frame_positions = vec![positions.clone()];
+    let mut current = positions.clone();
+    for &(robot, direction) in movements {
+        current = current.move_in_direction(round.board(), robot, direction);
+        frame_positions.push(current.clone());
+    }
+
+    let side_length = round.board().side_length() as u32;
+    let mut svg = String::new();
+    open_svg(&mut svg, side_length);
+    push_walls(&mut svg, round, side_length);
+    push_target(&mut svg, round);
+    for (i, frame) in frame_positions.iter().enumerate() {
+        svg.push_str(&format!(
+            "<g id=\"frame-{}\" style=\"display:{}\">\n",
+            i,
+            if i == 0 { "inline" } else { "none" }
+        ));
+        push_robots(&mut svg, frame);
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</svg>\n");
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head><meta charset=\"utf-8\"><title>Ricochet Robots solution</title></head>\n\
+         <body>\n\
+         {svg}\
+         <p><button onclick=\"step(-1)\">&larr; prev</button> \
+         <span id=\"frame-label\">1</span> / {frame_count} \
+         <button onclick=\"step(1)\">next &rarr;</button></p>\n\
+         <script>\n\
+         var frameCount = {frame_count};\n\
+         var frame = 0;\n\
+         function step(delta) {{\n\
+         \x20 document.getElementById('frame-' + frame).style.display = 'none';\n\
+         \x20 frame = (frame + delta + frameCount) % frameCount;\n\
+         \x20 document.getElementById('frame-' + frame).style.display = 'inline';\n\
+         \x20 document.getElementById('frame-label').textContent = frame + 1;\n\
+         }}\n\
+         </script>\n\
+         </body>\n\
+         </html>\n",
+        svg = svg,
+        frame_count = frame_positions.len(),
+    )
+}
+
+/// Writes the opening `<svg>` tag sized for a board of `side_length` fields.
+fn open_svg(svg: &mut String, side_length: u32) {
+    let canvas = side_length * SVG_CELL_SIZE;
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" \
+         viewBox=\"0 0 {0} {0}\">\n\
+         <rect x=\"0\" y=\"0\" width=\"{0}\" height=\"{0}\" fill=\"white\" stroke=\"black\" \
+         stroke-width=\"{1}\" />\n",
+        canvas, WALL_STROKE,
+    ));
+}
+
+/// Draws every inner wall segment set on `round`'s board.
+fn push_walls(svg: &mut String, round: &Round, side_length: u32) {
+    let walls = round.board().get_walls();
+    for col in 0..side_length as usize {
+        for row in 0..side_length as usize {
+            let field = walls[col][row];
+            let x = col as u32 * SVG_CELL_SIZE;
+            let y = row as u32 * SVG_CELL_SIZE;
+
+            if field.right {
+                push_line(svg, x + SVG_CELL_SIZE, y, x + SVG_CELL_SIZE, y + SVG_CELL_SIZE);
+            }
+            if field.down {
+                push_line(svg, x, y + SVG_CELL_SIZE, x + SVG_CELL_SIZE, y + SVG_CELL_SIZE);
+            }
+        }
+    }
+}
+
+/// Writes a single wall line from `(x1, y1)` to `(x2, y2)`.
+fn push_line(svg: &mut String, x1: u32, y1: u32, x2: u32, y2: u32) {
+    svg.push_str(&format!(
+        "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\" stroke-width=\"{}\" />\n",
+        x1, y1, x2, y2, WALL_STROKE,
+    ));
+}
+
+/// Fills `round`'s target field with its color and marks its symbol.
+fn push_target(svg: &mut String, round: &Round) {
+    let pos = round.target_position();
+    let x = pos.column() as u32 * SVG_CELL_SIZE;
+    let y = pos.row() as u32 * SVG_CELL_SIZE;
+    let color = target_color(round.target()).map_or("lightgray", hex);
+
+    svg.push_str(&format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" opacity=\"0.5\" />\n",
+        x, y, SVG_CELL_SIZE, SVG_CELL_SIZE, color,
+    ));
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\" \
+         font-size=\"{}\">{}</text>\n",
+        x + SVG_CELL_SIZE / 2,
+        y + SVG_CELL_SIZE / 2,
+        SVG_CELL_SIZE / 2,
+        target_symbol_glyph(round.target()),
+    ));
+}
+
+/// Draws every `Robot` in `positions` as a circle of its color.
+fn push_robots(svg: &mut String, positions: &RobotPositions) {
+    for &robot in &ROBOTS {
+        let pos = positions[robot];
+        let cx = pos.column() as u32 * SVG_CELL_SIZE + SVG_CELL_SIZE / 2;
+        let cy = pos.row() as u32 * SVG_CELL_SIZE + SVG_CELL_SIZE / 2;
+        svg.push_str(&format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"black\" stroke-width=\"2\" />\n",
+            cx,
+            cy,
+            SVG_CELL_SIZE / 3,
+            hex(robot_color(robot)),
+        ));
+    }
+}
+
+/// The SVG fill color for `color`.
+fn hex(color: Color) -> &'static str {
+    match color {
+        Color::Red => "#d62728",
+        Color::Blue => "#1f77b4",
+        Color::Green => "#2ca02c",
+        Color::Yellow => "#e6c300",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{draw_html, draw_svg};
+    use crate::{Board, Position, Robot, RobotPositions, Round, Symbol, Target};
+
+    fn create_round() -> (Round, RobotPositions) {
+        let board = Board::new_empty(3).wall_enclosure();
+        let round = Round::new(board, Target::Green(Symbol::Hexagon), Position::new(2, 2));
+        let positions = RobotPositions::from_tuples(&[(0, 0), (1, 0), (2, 0), (0, 1)]);
+        (round, positions)
+    }
+
+    #[test]
+    fn draw_svg_contains_the_target_color_and_every_robot() {
+        let (round, positions) = create_round();
+        let output = draw_svg(&round, &positions);
+
+        assert!(output.starts_with("<svg"));
+        assert!(output.contains("#2ca02c"), "target color missing:\n{}", output);
+        assert!(output.contains("#d62728"), "red robot missing:\n{}", output);
+        assert!(output.contains("#1f77b4"), "blue robot missing:\n{}", output);
+        assert!(output.contains("#e6c300"), "yellow robot missing:\n{}", output);
+    }
+
+    #[test]
+    fn draw_html_has_one_frame_per_move_plus_the_start() {
+        let (round, positions) = create_round();
+        let movements = [(Robot::Red, crate::Direction::Right)];
+
+        let output = draw_html(&round, &positions, &movements);
+
+        assert!(output.contains("<!DOCTYPE html>"));
+        assert!(output.contains("id=\"frame-0\""));
+        assert!(output.contains("id=\"frame-1\""));
+        assert!(!output.contains("id=\"frame-2\""));
+        assert!(output.contains("frameCount = 2"));
+    }
+
+    #[test]
+    fn draw_html_with_no_movements_has_a_single_frame() {
+        let (round, positions) = create_round();
+
+        let output = draw_html(&round, &positions, &[]);
+
+        assert!(output.contains("id=\"frame-0\""));
+        assert!(!output.contains("id=\"frame-1\""));
+        assert!(output.contains("frameCount = 1"));
+    }
+}
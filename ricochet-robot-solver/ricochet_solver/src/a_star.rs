@@ -1,38 +1,122 @@
-use fxhash::FxBuildHasher;
+use chrono::{Duration, Local};
+use fxhash::FxHashMap;
+use getset::Getters;
 use priority_queue::PriorityQueue;
-use ricochet_board::{RobotPositions, Round};
+use ricochet_board::{Direction, Robot, RobotPositions, Round};
 use std::cmp::Reverse;
 use std::usize;
 
-use crate::util::{BasicVisitedNode, LeastMovesBoard, VisitedNodes};
+use crate::util::{BasicVisitedNode, LeastMovesBoard, RayTable, VisitedNodes};
+use crate::zobrist::{PassthroughBuildHasher, PositionKey, ZobristTable};
 use crate::{Path, Solver};
 
+/// Error returned by [`AStar::solve_bounded`](AStar::solve_bounded).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// No robot configuration can reach the target from the starting positions.
+    Unsolvable,
+    /// The configured timeout or node-expansion cap was hit before the target was found.
+    ///
+    /// Contains the best partial path found so far, ending on the node with the lowest estimated
+    /// number of moves to the target.
+    BudgetExceeded(Path),
+}
+
+/// Result of [`AStar::solve_all_bounded`](AStar::solve_all_bounded), pairing the shortest paths
+/// found with whether enumeration was cut short before every one of them could be collected.
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct AllSolutions {
+    /// Every distinct shortest path found, up to the requested `max_solutions`.
+    paths: Vec<Path>,
+    /// `true` if the configured timeout or expansion cap cut the search short, or if more than
+    /// `max_solutions` distinct optimal paths existed and had to be dropped.
+    truncated: bool,
+}
+
+impl AllSolutions {
+    /// Creates a new result pairing the paths found with whether enumeration was cut short.
+    pub(crate) fn new(paths: Vec<Path>, truncated: bool) -> Self {
+        Self { paths, truncated }
+    }
+}
+
 /// A solver using the [A*](https://en.wikipedia.org/wiki/A*_search_algorithm) search algorithm to
 /// find a path to the target.
 ///
 /// It uses a [`LeastMovesBoard`](LeastMovesBoard) as an admissable heuristic to prioritize the
-/// visited nodes.
+/// visited nodes. [`solve`](Solver::solve) always searches for a provably optimal path and panics
+/// if the round turns out to be unsolvable; use [`solve_bounded`](AStar::solve_bounded) together
+/// with [`with_weight`](AStar::with_weight), [`with_timeout`](AStar::with_timeout), and
+/// [`with_max_expansions`](AStar::with_max_expansions) for an anytime solver with a search budget.
+/// [`solve_all`](AStar::solve_all) and its budgeted counterpart
+/// [`solve_all_bounded`](AStar::solve_all_bounded) enumerate every distinct shortest path instead
+/// of just one.
 #[derive(Debug)]
 pub struct AStar {
     visited_nodes: VisitedNodes<BasicVisitedNode>,
     move_board: LeastMovesBoard,
+    /// Precomputed wall-stop rays for the board of the round currently being solved.
+    ray_table: RayTable,
+    weight: f64,
+    timeout: Option<Duration>,
+    max_expansions: Option<usize>,
 }
 
 impl AStar {
-    /// Creates a new `AStar` solver.
+    /// Creates a new `AStar` solver searching for a provably optimal path with no search budget.
     pub fn new() -> Self {
         Self {
             visited_nodes: VisitedNodes::with_capacity(65536),
             move_board: Default::default(),
+            ray_table: Default::default(),
+            weight: 1.0,
+            timeout: None,
+            max_expansions: None,
         }
     }
-}
 
-impl Solver for AStar {
-    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+    /// Inflates the admissible heuristic by `weight` inside the priority ordering, trading
+    /// optimality for speed.
+    ///
+    /// The search becomes bounded-suboptimal: the returned path is guaranteed to be at most a
+    /// factor `weight` longer than the optimal one.
+    ///
+    /// # Panics
+    /// Panics if `weight` is less than `1.0`.
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        assert!(
+            weight >= 1.0,
+            "the heuristic weight has to be at least 1.0 to keep the search bounded-suboptimal"
+        );
+        self.weight = weight;
+        self
+    }
+
+    /// Stops the search once `timeout` has elapsed since the start of
+    /// [`solve_bounded`](AStar::solve_bounded), returning the best partial path found so far.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Stops the search after expanding `max_expansions` nodes, returning the best partial path
+    /// found so far.
+    pub fn with_max_expansions(mut self, max_expansions: usize) -> Self {
+        self.max_expansions = Some(max_expansions);
+        self
+    }
+
+    /// Finds a path to the target, honoring the configured weight, timeout, and expansion cap
+    /// instead of panicking or running unbounded.
+    pub fn solve_bounded(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+    ) -> Result<Path, SolveError> {
         // Check if the target has already been reached.
         if round.target_reached(&start_positions) {
-            return Path::new_start_on_target(start_positions);
+            return Ok(Path::new_start_on_target(start_positions));
         }
 
         // Check if the problem may be impossible to solve.
@@ -41,45 +125,76 @@ impl Solver for AStar {
             .move_board
             .is_unsolvable(&start_positions, round.target())
         {
-            panic!("It's not possible to reach the target starting from this robot configuration");
+            return Err(SolveError::Unsolvable);
         }
+        self.ray_table = RayTable::new(round.board());
 
         // Use the least moves board as an admissable heuristic (never overestimates the moves needed).
         let move_board_ref = &self.move_board;
         let moves_to_target = |pos: &RobotPositions| move_board_ref.min_moves(pos, round.target());
+        let weight = self.weight;
+
+        // Precompute random values to key the open list by a cheap 64-bit hash instead of
+        // rehashing the full `RobotPositions` on every priority lookup, see `crate::zobrist`.
+        let zobrist = ZobristTable::new(round.board().side_length());
+        let start_hash = zobrist.hash(&start_positions);
 
         // Create a queue holding the not yet expanded nodes.
-        let mut open_list =
-            PriorityQueue::<RobotPositions, MoveCounter, FxBuildHasher>::with_capacity_and_hasher(
-                65536,
-                Default::default(),
-            );
+        let mut open_list = PriorityQueue::<
+            PositionKey,
+            MoveCounter,
+            PassthroughBuildHasher,
+        >::with_capacity_and_hasher(65536, Default::default());
 
         // Add starting positions to the open list.
         open_list.push(
-            start_positions.clone(),
-            MoveCounter::new(0, moves_to_target(&start_positions)),
+            PositionKey::new(start_positions.clone(), start_hash),
+            MoveCounter::new(0, moves_to_target(&start_positions), weight),
         );
 
         let mut found_minimum = usize::MAX;
-        let mut found_final_position = start_positions;
+        let mut found_final_position = start_positions.clone();
+
+        // Tracks the node with the lowest `min_moves` estimate seen so far, to fall back on if
+        // the search runs out of its budget before reaching the target.
+        let mut best_estimate = moves_to_target(&start_positions);
+        let mut best_estimate_position: Option<RobotPositions> = None;
+
+        let start_time = Local::now();
+        let mut expansions = 0usize;
 
         // Expand the search tree.
-        while let Some((from_pos, prio)) = open_list.pop() {
+        while let Some((from_key, prio)) = open_list.pop() {
             if prio.total() >= found_minimum {
                 // The shortest path has been found.
                 break;
             }
 
-            for (pos, movement) in from_pos.reachable_positions(round.board()) {
+            expansions += 1;
+            if let Some(max_expansions) = self.max_expansions {
+                if expansions > max_expansions {
+                    return Err(SolveError::BudgetExceeded(
+                        self.best_partial_path(&start_positions, &best_estimate_position),
+                    ));
+                }
+            }
+
+            let from_pos = from_key.positions();
+            for (pos, movement) in self.ray_table.reachable_positions(from_pos) {
                 let moves_from_start = prio.from_start() + 1;
                 let moves_to_target = moves_to_target(&pos);
+                let hash = zobrist.rehash_move(
+                    from_key.hash(),
+                    movement.0,
+                    from_pos[movement.0],
+                    pos[movement.0],
+                );
 
                 if self
                     .visited_nodes
                     .add_node(
                         pos.clone(),
-                        &from_pos,
+                        from_pos,
                         moves_from_start,
                         movement,
                         &BasicVisitedNode::new,
@@ -90,6 +205,11 @@ impl Solver for AStar {
                     continue;
                 }
 
+                if moves_to_target < best_estimate {
+                    best_estimate = moves_to_target;
+                    best_estimate_position = Some(pos.clone());
+                }
+
                 if round.target_reached(&pos) {
                     // A better solution has been found.
                     if moves_to_target < found_minimum {
@@ -99,11 +219,375 @@ impl Solver for AStar {
                     continue;
                 }
 
-                open_list.push_increase(pos, MoveCounter::new(moves_from_start, moves_to_target));
+                open_list.push_increase(
+                    PositionKey::new(pos, hash),
+                    MoveCounter::new(moves_from_start, moves_to_target, weight),
+                );
+            }
+
+            if let Some(timeout) = self.timeout {
+                if Local::now() - start_time >= timeout {
+                    return Err(SolveError::BudgetExceeded(
+                        self.best_partial_path(&start_positions, &best_estimate_position),
+                    ));
+                }
+            }
+        }
+
+        Ok(self.visited_nodes.path_to(&found_final_position))
+    }
+
+    /// Builds the best partial path found so far, falling back to a path staying on `start` if no
+    /// node has been expanded yet.
+    fn best_partial_path(
+        &self,
+        start: &RobotPositions,
+        best_estimate_position: &Option<RobotPositions>,
+    ) -> Path {
+        match best_estimate_position {
+            Some(pos) => self.visited_nodes.path_to(pos),
+            None => Path::new_start_on_target(start.clone()),
+        }
+    }
+
+    /// Finds every distinct path of the proven minimum length, up to `max_solutions`.
+    ///
+    /// Unlike [`solve`](Solver::solve), a node rediscovered with a number of moves *equal* to its
+    /// known best is not discarded: the new predecessor edge is recorded alongside the existing
+    /// ones, building a DAG of optimal predecessors instead of a single-parent tree. The DAG is
+    /// then back-traced from every optimal final position to enumerate the cartesian set of
+    /// shortest paths.
+    ///
+    /// # Panics
+    /// Panics if the round can't be solved from `start_positions`.
+    pub fn solve_all(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        max_solutions: usize,
+    ) -> Vec<Path> {
+        if round.target_reached(&start_positions) {
+            return vec![Path::new_start_on_target(start_positions)];
+        }
+
+        self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
+        if self
+            .move_board
+            .is_unsolvable(&start_positions, round.target())
+        {
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+        self.ray_table = RayTable::new(round.board());
+
+        let move_board_ref = &self.move_board;
+        let moves_to_target = |pos: &RobotPositions| move_board_ref.min_moves(pos, round.target());
+
+        let zobrist = ZobristTable::new(round.board().side_length());
+        let start_hash = zobrist.hash(&start_positions);
+
+        let mut open_list = PriorityQueue::<
+            PositionKey,
+            MoveCounter,
+            PassthroughBuildHasher,
+        >::with_capacity_and_hasher(65536, Default::default());
+        open_list.push(
+            PositionKey::new(start_positions.clone(), start_hash),
+            MoveCounter::new(0, moves_to_target(&start_positions), 1.0),
+        );
+
+        // Every position reached so far maps to the moves needed to reach it and every edge
+        // (predecessor, movement) that reaches it in that number of moves.
+        let mut moves_to_reach: FxHashMap<RobotPositions, usize> = FxHashMap::default();
+        let mut predecessors: OptimalPredecessors = FxHashMap::default();
+        moves_to_reach.insert(start_positions.clone(), 0);
+
+        let mut found_minimum = usize::MAX;
+        let mut found_final_positions = Vec::new();
+
+        while let Some((from_key, prio)) = open_list.pop() {
+            if prio.total() > found_minimum {
+                // The whole f == found_minimum contour has been expanded, so every optimal path
+                // has been found; unlike `solve`, stopping at `>=` would drop nodes still queued
+                // with f == found_minimum that haven't been expanded yet.
+                break;
+            }
+
+            let from_pos = from_key.positions();
+            let from_moves = prio.from_start();
+
+            for (pos, movement) in self.ray_table.reachable_positions(from_pos) {
+                let moves_from_start = from_moves + 1;
+
+                match moves_to_reach.get(&pos) {
+                    Some(&known) if known < moves_from_start => continue,
+                    Some(&known) if known == moves_from_start => {
+                        predecessors
+                            .get_mut(&pos)
+                            .expect("a node with a known move count always has predecessors")
+                            .push((from_pos.clone(), movement));
+                        continue;
+                    }
+                    _ => {
+                        moves_to_reach.insert(pos.clone(), moves_from_start);
+                        predecessors.insert(pos.clone(), vec![(from_pos.clone(), movement)]);
+                    }
+                }
+
+                if round.target_reached(&pos) {
+                    match moves_from_start.cmp(&found_minimum) {
+                        std::cmp::Ordering::Less => {
+                            found_minimum = moves_from_start;
+                            found_final_positions = vec![pos];
+                        }
+                        std::cmp::Ordering::Equal => found_final_positions.push(pos),
+                        std::cmp::Ordering::Greater => {}
+                    }
+                    continue;
+                }
+
+                let hash = zobrist.rehash_move(
+                    from_key.hash(),
+                    movement.0,
+                    from_pos[movement.0],
+                    pos[movement.0],
+                );
+                open_list.push_increase(
+                    PositionKey::new(pos.clone(), hash),
+                    MoveCounter::new(moves_from_start, moves_to_target(&pos), 1.0),
+                );
+            }
+        }
+
+        let mut paths = Vec::new();
+        for final_pos in found_final_positions {
+            if paths.len() >= max_solutions {
+                break;
+            }
+            let mut movements = Vec::with_capacity(found_minimum);
+            backtrace_optimal_paths(
+                &predecessors,
+                &start_positions,
+                &final_pos,
+                &final_pos,
+                &mut movements,
+                max_solutions,
+                &mut paths,
+            );
+        }
+        paths.truncate(max_solutions);
+        paths
+    }
+
+    /// Like [`solve_all`](AStar::solve_all), but honors the configured
+    /// [`timeout`](AStar::with_timeout) and [`max_expansions`](AStar::with_max_expansions) instead
+    /// of running until every optimal path is proven found.
+    ///
+    /// Returns an [`AllSolutions`](AllSolutions) pairing the paths found so far with a `truncated`
+    /// flag, set if the timeout or expansion cap cut the search short, or if more than
+    /// `max_solutions` distinct optimal paths existed.
+    ///
+    /// # Errors
+    /// Returns [`SolveError::Unsolvable`](SolveError::Unsolvable) if the round can't be solved from
+    /// `start_positions`. Never returns [`SolveError::BudgetExceeded`](SolveError::BudgetExceeded);
+    /// a budget running out is instead reported through `AllSolutions::truncated`.
+    pub fn solve_all_bounded(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        max_solutions: usize,
+    ) -> Result<AllSolutions, SolveError> {
+        if round.target_reached(&start_positions) {
+            return Ok(AllSolutions::new(
+                vec![Path::new_start_on_target(start_positions)],
+                false,
+            ));
+        }
+
+        self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
+        if self
+            .move_board
+            .is_unsolvable(&start_positions, round.target())
+        {
+            return Err(SolveError::Unsolvable);
+        }
+        self.ray_table = RayTable::new(round.board());
+
+        let move_board_ref = &self.move_board;
+        let moves_to_target = |pos: &RobotPositions| move_board_ref.min_moves(pos, round.target());
+
+        let zobrist = ZobristTable::new(round.board().side_length());
+        let start_hash = zobrist.hash(&start_positions);
+
+        let mut open_list = PriorityQueue::<
+            PositionKey,
+            MoveCounter,
+            PassthroughBuildHasher,
+        >::with_capacity_and_hasher(65536, Default::default());
+        open_list.push(
+            PositionKey::new(start_positions.clone(), start_hash),
+            MoveCounter::new(0, moves_to_target(&start_positions), 1.0),
+        );
+
+        let mut moves_to_reach: FxHashMap<RobotPositions, usize> = FxHashMap::default();
+        let mut predecessors: OptimalPredecessors = FxHashMap::default();
+        moves_to_reach.insert(start_positions.clone(), 0);
+
+        let mut found_minimum = usize::MAX;
+        let mut found_final_positions = Vec::new();
+
+        let start_time = Local::now();
+        let mut expansions = 0usize;
+        let mut truncated = false;
+
+        while let Some((from_key, prio)) = open_list.pop() {
+            if prio.total() > found_minimum {
+                // See the matching comment in `solve_all`: the full f == found_minimum contour
+                // must be expanded, not just reached, to enumerate every optimal path.
+                break;
+            }
+
+            expansions += 1;
+            if let Some(max_expansions) = self.max_expansions {
+                if expansions > max_expansions {
+                    truncated = true;
+                    break;
+                }
+            }
+
+            let from_pos = from_key.positions();
+            let from_moves = prio.from_start();
+
+            for (pos, movement) in self.ray_table.reachable_positions(from_pos) {
+                let moves_from_start = from_moves + 1;
+
+                match moves_to_reach.get(&pos) {
+                    Some(&known) if known < moves_from_start => continue,
+                    Some(&known) if known == moves_from_start => {
+                        predecessors
+                            .get_mut(&pos)
+                            .expect("a node with a known move count always has predecessors")
+                            .push((from_pos.clone(), movement));
+                        continue;
+                    }
+                    _ => {
+                        moves_to_reach.insert(pos.clone(), moves_from_start);
+                        predecessors.insert(pos.clone(), vec![(from_pos.clone(), movement)]);
+                    }
+                }
+
+                if round.target_reached(&pos) {
+                    match moves_from_start.cmp(&found_minimum) {
+                        std::cmp::Ordering::Less => {
+                            found_minimum = moves_from_start;
+                            found_final_positions = vec![pos];
+                        }
+                        std::cmp::Ordering::Equal => found_final_positions.push(pos),
+                        std::cmp::Ordering::Greater => {}
+                    }
+                    continue;
+                }
+
+                let hash = zobrist.rehash_move(
+                    from_key.hash(),
+                    movement.0,
+                    from_pos[movement.0],
+                    pos[movement.0],
+                );
+                open_list.push_increase(
+                    PositionKey::new(pos.clone(), hash),
+                    MoveCounter::new(moves_from_start, moves_to_target(&pos), 1.0),
+                );
+            }
+
+            if let Some(timeout) = self.timeout {
+                if Local::now() - start_time >= timeout {
+                    truncated = true;
+                    break;
+                }
+            }
+        }
+
+        let mut paths = Vec::new();
+        for final_pos in found_final_positions {
+            if paths.len() >= max_solutions {
+                truncated = true;
+                break;
+            }
+            let mut movements = Vec::with_capacity(found_minimum);
+            if backtrace_optimal_paths(
+                &predecessors,
+                &start_positions,
+                &final_pos,
+                &final_pos,
+                &mut movements,
+                max_solutions,
+                &mut paths,
+            ) {
+                truncated = true;
             }
         }
+        paths.truncate(max_solutions);
+
+        Ok(AllSolutions::new(paths, truncated))
+    }
+}
+
+/// For every reached position, every `(predecessor, movement)` edge that reaches it in the
+/// minimum known number of moves, see [`AStar::solve_all`](AStar::solve_all).
+type OptimalPredecessors = FxHashMap<RobotPositions, Vec<(RobotPositions, (Robot, Direction))>>;
+
+/// Recursively walks `predecessors` backwards from `current` to `start`, pushing a completed
+/// [`Path`](Path) for every distinct route found, until `paths` holds `max_solutions` of them.
+///
+/// Returns `true` if enumeration had to stop early because `max_solutions` was reached while
+/// routes through `current` remained unexplored.
+fn backtrace_optimal_paths(
+    predecessors: &OptimalPredecessors,
+    start: &RobotPositions,
+    end: &RobotPositions,
+    current: &RobotPositions,
+    movements: &mut Vec<(Robot, Direction)>,
+    max_solutions: usize,
+    paths: &mut Vec<Path>,
+) -> bool {
+    if paths.len() >= max_solutions {
+        return true;
+    }
+
+    if current == start {
+        let mut path_movements = movements.clone();
+        path_movements.reverse();
+        paths.push(Path::new(start.clone(), end.clone(), path_movements));
+        return false;
+    }
+
+    let mut truncated = false;
+    for (previous, movement) in &predecessors[current] {
+        if paths.len() >= max_solutions {
+            truncated = true;
+            break;
+        }
+        movements.push(*movement);
+        if backtrace_optimal_paths(predecessors, start, end, previous, movements, max_solutions, paths)
+        {
+            truncated = true;
+        }
+        movements.pop();
+    }
+    truncated
+}
 
-        self.visited_nodes.path_to(&found_final_position)
+impl Solver for AStar {
+    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+        match self.solve_bounded(round, start_positions) {
+            Ok(path) => path,
+            Err(SolveError::Unsolvable) => {
+                panic!("It's not possible to reach the target starting from this robot configuration")
+            }
+            Err(SolveError::BudgetExceeded(_)) => {
+                unreachable!("solve() never configures a timeout or an expansion cap")
+            }
+        }
     }
 }
 
@@ -133,9 +617,12 @@ struct MoveCounter {
 }
 
 impl MoveCounter {
-    pub fn new(from_start: usize, to_target: usize) -> Self {
+    /// Creates a new counter, inflating `to_target` by `weight` before adding it to `from_start`
+    /// to obtain the total (weighted A*, see [`AStar::with_weight`](AStar::with_weight)).
+    pub fn new(from_start: usize, to_target: usize, weight: f64) -> Self {
+        let weighted_to_target = (to_target as f64 * weight).round() as usize;
         Self {
-            total: Reverse(from_start + to_target),
+            total: Reverse(from_start + weighted_to_target),
             from_start: Reverse(from_start),
         }
     }
@@ -151,10 +638,11 @@ impl MoveCounter {
 
 #[cfg(test)]
 mod tests {
+    use chrono::Duration;
     use priority_queue::PriorityQueue;
     use ricochet_board::{quadrant, Direction, Game, Robot, RobotPositions, Round, Symbol, Target};
 
-    use super::{AStar, MoveCounter, Path, Solver};
+    use super::{AStar, MoveCounter, Path, SolveError, Solver};
 
     fn create_board() -> (RobotPositions, Game) {
         let quadrants = quadrant::gen_quadrants()
@@ -180,10 +668,10 @@ mod tests {
     #[test]
     fn move_counter_ordering() {
         // naming scheme: total_fromStart
-        let ten_five = MoveCounter::new(5, 5);
-        let ten_three_1 = MoveCounter::new(3, 7);
-        let ten_three_2 = MoveCounter::new(3, 7);
-        let five_two = MoveCounter::new(2, 3);
+        let ten_five = MoveCounter::new(5, 5, 1.0);
+        let ten_three_1 = MoveCounter::new(3, 7, 1.0);
+        let ten_three_2 = MoveCounter::new(3, 7, 1.0);
+        let five_two = MoveCounter::new(2, 3, 1.0);
         let mut sorted = vec![
             ten_three_1.clone(),
             five_two.clone(),
@@ -198,15 +686,25 @@ mod tests {
     #[test]
     fn move_counter_priority_queue() {
         let mut queue = PriorityQueue::new();
-        queue.push("first", MoveCounter::new(3, 7));
-        queue.push("second", MoveCounter::new(2, 3));
-        queue.push("third", MoveCounter::new(5, 5));
-        queue.push("fourth", MoveCounter::new(3, 7));
+        queue.push("first", MoveCounter::new(3, 7, 1.0));
+        queue.push("second", MoveCounter::new(2, 3, 1.0));
+        queue.push("third", MoveCounter::new(5, 5, 1.0));
+        queue.push("fourth", MoveCounter::new(3, 7, 1.0));
 
         let expected = queue.into_sorted_vec();
         assert_eq!(vec!["second", "fourth", "first", "third"], expected)
     }
 
+    #[test]
+    fn move_counter_weighted() {
+        // Inflating the heuristic raises the total but never the `from_start` tie-breaker.
+        let unweighted = MoveCounter::new(2, 4, 1.0);
+        let weighted = MoveCounter::new(2, 4, 2.0);
+        assert_eq!(unweighted.total(), 6);
+        assert_eq!(weighted.total(), 10);
+        assert_eq!(unweighted.from_start(), weighted.from_start());
+    }
+
     // Test robot already on target
     #[test]
     fn on_target() {
@@ -253,4 +751,176 @@ mod tests {
 
         assert_eq!(AStar::new().solve(&round, pos), expected);
     }
+
+    #[test]
+    fn solve_bounded_reports_unsolvable() {
+        let board = ricochet_board::Board::new_empty(2)
+            .wall_enclosure()
+            .set_vertical_line(0, 0, 1)
+            .set_horizontal_line(0, 0, 1);
+        let target_position = ricochet_board::Position::new(1, 0);
+        let round = Round::new(board, Target::Spiral, target_position);
+        let pos = RobotPositions::from_tuples(&[(0, 0), (0, 0), (0, 0), (0, 0)]);
+
+        assert_eq!(
+            AStar::new().solve_bounded(&round, pos),
+            Err(SolveError::Unsolvable)
+        );
+    }
+
+    #[test]
+    fn solve_bounded_within_budget_matches_solve() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let expected = AStar::new().solve(&round, pos.clone());
+        let bounded = AStar::new()
+            .with_max_expansions(100_000)
+            .solve_bounded(&round, pos)
+            .expect("the round is solvable well within the expansion cap");
+        assert_eq!(expected, bounded);
+    }
+
+    #[test]
+    fn solve_bounded_stops_at_expansion_cap() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let result = AStar::new().with_max_expansions(1).solve_bounded(&round, pos);
+        assert!(matches!(result, Err(SolveError::BudgetExceeded(_))));
+    }
+
+    #[test]
+    fn solve_all_includes_the_single_shortest_path() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let shortest = AStar::new().solve(&round, pos.clone());
+        let all = AStar::new().solve_all(&round, pos, 10);
+
+        assert!(all.iter().all(|path| path.len() == shortest.len()));
+        assert!(all.contains(&shortest));
+    }
+
+    #[test]
+    fn solve_all_respects_max_solutions() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let all = AStar::new().solve_all(&round, pos, 1);
+        assert_eq!(all.len(), 1);
+    }
+
+    #[test]
+    fn solve_all_on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new_start_on_target(start.clone());
+        assert_eq!(AStar::new().solve_all(&round, start, 10), vec![expected]);
+    }
+
+    #[test]
+    fn solve_all_bounded_matches_solve_all_within_budget() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let all = AStar::new().solve_all(&round, pos.clone(), 10);
+        let bounded = AStar::new()
+            .with_max_expansions(100_000)
+            .with_timeout(Duration::seconds(10))
+            .solve_all_bounded(&round, pos, 10)
+            .expect("the round is solvable well within the budget");
+
+        assert!(!bounded.truncated());
+        assert_eq!(bounded.paths(), &all);
+    }
+
+    #[test]
+    fn solve_all_bounded_reports_unsolvable() {
+        let board = ricochet_board::Board::new_empty(2)
+            .wall_enclosure()
+            .set_vertical_line(0, 0, 1)
+            .set_horizontal_line(0, 0, 1);
+        let target_position = ricochet_board::Position::new(1, 0);
+        let round = Round::new(board, Target::Spiral, target_position);
+        let pos = RobotPositions::from_tuples(&[(0, 0), (0, 0), (0, 0), (0, 0)]);
+
+        assert_eq!(
+            AStar::new().solve_all_bounded(&round, pos, 10),
+            Err(SolveError::Unsolvable)
+        );
+    }
+
+    #[test]
+    fn solve_all_bounded_flags_truncation_at_expansion_cap() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let bounded = AStar::new()
+            .with_max_expansions(1)
+            .solve_all_bounded(&round, pos, 10)
+            .expect("Unsolvable is only returned before any expansion happens");
+
+        assert!(bounded.truncated());
+    }
+
+    #[test]
+    fn solve_all_bounded_flags_truncation_at_max_solutions() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let all = AStar::new().solve_all(&round, pos.clone(), 10);
+        if all.len() < 2 {
+            // Nothing to truncate on this board/target; the cap-vs-enumeration distinction only
+            // shows up when more than one optimal path actually exists.
+            return;
+        }
+
+        let bounded = AStar::new()
+            .solve_all_bounded(&round, pos, 1)
+            .expect("the round is solvable");
+
+        assert_eq!(bounded.paths().len(), 1);
+        assert!(bounded.truncated());
+    }
 }
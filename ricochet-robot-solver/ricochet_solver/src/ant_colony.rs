@@ -0,0 +1,522 @@
+use chrono::{Duration, Local};
+use fxhash::{FxHashMap, FxHashSet};
+use rand::{Rng, SeedableRng};
+use ricochet_board::{Direction, Robot, RobotPositions, Round};
+
+use crate::util::LeastMovesBoard;
+use crate::{Path, Solver};
+
+/// Pheromone value a previously unvisited edge starts out with.
+const DEFAULT_PHEROMONE: f64 = 1.0;
+
+/// A stochastic solver using [Ant Colony Optimization](https://en.wikipedia.org/wiki/Ant_colony_optimization_algorithms)
+/// to find a short path to the target.
+///
+/// Unlike [`AStar`](crate::AStar) or [`BreadthFirst`](crate::BreadthFirst), `AntColony` doesn't
+/// exhaustively expand the search frontier, which makes it usable on boards where that frontier
+/// would otherwise become unmanageable. It trades the guarantee of an optimal solution for a
+/// bounded amount of work per iteration: every iteration, `ant_count` ants independently walk from
+/// the starting positions, favoring edges `(RobotPositions, (Robot, Direction))` with more
+/// pheromone and a lower [`LeastMovesBoard`](LeastMovesBoard) estimate to the target. A walk never
+/// revisits a position it has already crossed, so ants can't cycle back on themselves. Afterwards
+/// every pheromone value evaporates by a factor of `1 - rho`, every ant that reached the target
+/// deposits pheromone on the edges it used, proportional to how short its path was, and, if
+/// [`with_elitism`](AntColony::with_elitism) is enabled, the single best walk found across every
+/// iteration so far deposits an extra reward on top, so the best-known route keeps dominating
+/// instead of being washed out by evaporation. The shortest path found across all iterations is
+/// returned, or the best-effort path found so far once an optional
+/// [`with_time_budget`](AntColony::with_time_budget) wall-clock deadline is reached, regardless of
+/// how many iterations are left. If no ant ever reaches the target at all, the walk that got
+/// closest is returned instead: its end position won't satisfy the round's target, which callers
+/// can check for, rather than this solver claiming the start was already on target.
+#[derive(Debug, Clone)]
+pub struct AntColony {
+    /// Pheromone value of every edge an ant has walked so far.
+    pheromone: FxHashMap<(RobotPositions, (Robot, Direction)), f64>,
+    /// This board contains the minimum number of moves to reach the target for each field.
+    move_board: LeastMovesBoard,
+    /// Number of ants dispatched per iteration.
+    ant_count: usize,
+    /// Number of iterations of ant dispatch, evaporation, and deposit.
+    iterations: usize,
+    /// Maximum number of moves a single ant is allowed to make before giving up.
+    max_steps: usize,
+    /// Controls how strongly pheromone influences an ant's next move, relative to `beta`.
+    alpha: f64,
+    /// Controls how strongly the heuristic desirability influences an ant's next move, relative to
+    /// `alpha`.
+    beta: f64,
+    /// Fraction of pheromone that evaporates after each iteration.
+    rho: f64,
+    /// Amount of pheromone deposited on an edge of a path of length 1, divided among the edges of
+    /// longer paths.
+    q: f64,
+    /// Whether the globally best walk found so far deposits an extra reward every iteration, on top
+    /// of whatever that iteration's ants deposited. See [`with_elitism`](Self::with_elitism).
+    elitism: bool,
+    /// Wall-clock budget for `solve`, checked between iterations; `None` means run for exactly
+    /// `iterations` rounds.
+    time_budget: Option<Duration>,
+    rng: rand_pcg::Pcg64Mcg,
+}
+
+impl AntColony {
+    /// Creates a new `AntColony` solver with default settings.
+    pub fn new() -> Self {
+        Self {
+            pheromone: FxHashMap::default(),
+            move_board: Default::default(),
+            ant_count: 32,
+            iterations: 100,
+            max_steps: 64,
+            alpha: 1.0,
+            beta: 2.0,
+            rho: 0.1,
+            q: 1.0,
+            elitism: false,
+            time_budget: None,
+            rng: rand_pcg::Pcg64Mcg::from_entropy(),
+        }
+    }
+
+    /// Sets the number of ants dispatched per iteration.
+    pub fn with_ant_count(mut self, ant_count: usize) -> Self {
+        self.ant_count = ant_count;
+        self
+    }
+
+    /// Sets the number of iterations of ant dispatch, evaporation, and deposit.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the maximum number of moves a single ant is allowed to make before giving up.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Sets `alpha`, controlling how strongly pheromone influences an ant's next move.
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Sets `beta`, controlling how strongly the heuristic desirability influences an ant's next
+    /// move.
+    pub fn with_beta(mut self, beta: f64) -> Self {
+        self.beta = beta;
+        self
+    }
+
+    /// Sets `rho`, the fraction of pheromone that evaporates after each iteration.
+    ///
+    /// # Panics
+    /// Panics if `rho` isn't within `0.0..=1.0`.
+    pub fn with_evaporation_rate(mut self, rho: f64) -> Self {
+        assert!((0.0..=1.0).contains(&rho), "rho has to be within 0.0..=1.0");
+        self.rho = rho;
+        self
+    }
+
+    /// Enables elitist reinforcement: every iteration, the globally best walk found so far deposits
+    /// an extra reward on top of whatever that iteration's ants deposited, so it keeps dominating
+    /// the pheromone map instead of being washed out by evaporation before the search converges on
+    /// it.
+    pub fn with_elitism(mut self, elitism: bool) -> Self {
+        self.elitism = elitism;
+        self
+    }
+
+    /// Gives up after `budget` wall-clock time has elapsed, in addition to (not instead of)
+    /// `iterations`, whichever comes first.
+    ///
+    /// The clock is only checked between iterations, not while an iteration's ants are walking, so
+    /// a single slow iteration can still run past the budget.
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Seeds the random number generator used to pick an ant's next move, for reproducible runs.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = rand_pcg::Pcg64Mcg::seed_from_u64(seed);
+        self
+    }
+
+    /// Lets a single ant walk from `start` until it reaches the target or hits `max_steps` moves,
+    /// picking the next move among `reachable_positions` with probability proportional to
+    /// `pheromone^alpha * desirability^beta`.
+    ///
+    /// A move back onto a position the ant has already visited on this walk is never considered, so
+    /// the walk can't cycle back on itself.
+    ///
+    /// Returns the positions and movements of the walk, and whether it reached the target; a walk
+    /// that ran out of `max_steps` or candidates still returns whatever progress it made, so a
+    /// failed walk can be used as an honest best-effort fallback instead of being thrown away.
+    fn walk(
+        &mut self,
+        round: &Round,
+        start: &RobotPositions,
+    ) -> (Vec<RobotPositions>, Vec<(Robot, Direction)>, bool) {
+        let mut positions = Vec::with_capacity(self.max_steps + 1);
+        let mut movements = Vec::with_capacity(self.max_steps);
+        let mut visited: FxHashSet<RobotPositions> = FxHashSet::default();
+        positions.push(start.clone());
+        visited.insert(start.clone());
+
+        let mut current = start.clone();
+        for _ in 0..self.max_steps {
+            let candidates: Vec<_> = current
+                .reachable_positions(round.board())
+                .filter(|(pos, _)| !visited.contains(pos))
+                .collect();
+            if candidates.is_empty() {
+                return (positions, movements, false);
+            }
+
+            let weights: Vec<f64> = candidates
+                .iter()
+                .map(|(pos, movement)| {
+                    let pheromone = *self
+                        .pheromone
+                        .get(&(current.clone(), *movement))
+                        .unwrap_or(&DEFAULT_PHEROMONE);
+                    let desirability =
+                        1.0 / (1.0 + self.move_board.min_moves(pos, round.target()) as f64);
+                    pheromone.powf(self.alpha) * desirability.powf(self.beta)
+                })
+                .collect();
+
+            let chosen = self.choose_weighted(&weights);
+            let (next_pos, movement) = candidates[chosen].clone();
+
+            movements.push(movement);
+            current = next_pos.clone();
+            visited.insert(next_pos.clone());
+            positions.push(next_pos);
+
+            if round.target_reached(&current) {
+                return (positions, movements, true);
+            }
+        }
+
+        (positions, movements, false)
+    }
+
+    /// Picks an index into `weights` with probability proportional to its value.
+    ///
+    /// Falls back to a uniform choice if every weight is zero.
+    fn choose_weighted(&mut self, weights: &[f64]) -> usize {
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return self.rng.gen_range(0..weights.len());
+        }
+
+        let mut threshold = self.rng.gen_range(0.0..total);
+        for (i, &weight) in weights.iter().enumerate() {
+            if threshold < weight {
+                return i;
+            }
+            threshold -= weight;
+        }
+        weights.len() - 1
+    }
+
+    /// Evaporates every pheromone value by a factor of `1 - self.rho`.
+    fn evaporate(&mut self) {
+        for value in self.pheromone.values_mut() {
+            *value *= 1.0 - self.rho;
+        }
+    }
+
+    /// Deposits `self.q / movements.len()` pheromone on every edge of a walk that reached the
+    /// target, so shorter walks reinforce their edges more strongly.
+    fn deposit(&mut self, positions: &[RobotPositions], movements: &[(Robot, Direction)]) {
+        let amount = self.q / movements.len() as f64;
+        for (pos, &movement) in positions.iter().zip(movements) {
+            *self
+                .pheromone
+                .entry((pos.clone(), movement))
+                .or_insert(DEFAULT_PHEROMONE) += amount;
+        }
+    }
+}
+
+impl Solver for AntColony {
+    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+        if round.target_reached(&start_positions) {
+            return Path::new_start_on_target(start_positions);
+        }
+
+        self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
+        if self
+            .move_board
+            .is_unsolvable(&start_positions, round.target())
+        {
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+
+        self.pheromone.clear();
+        let mut best: Option<(Vec<RobotPositions>, Vec<(Robot, Direction)>)> = None;
+        // The closest any ant has gotten without reaching the target, kept as an honest fallback
+        // for when none ever does: `(positions, movements, distance left to the target)`.
+        let mut best_partial: Option<(Vec<RobotPositions>, Vec<(Robot, Direction)>, usize)> = None;
+        let start_time = self.time_budget.map(|_| Local::now());
+
+        for _ in 0..self.iterations {
+            let walks: Vec<_> = (0..self.ant_count)
+                .map(|_| self.walk(round, &start_positions))
+                .collect();
+
+            self.evaporate();
+            for (positions, movements, reached) in &walks {
+                if *reached {
+                    self.deposit(positions, movements);
+                }
+            }
+
+            for (positions, movements, reached) in walks {
+                if reached {
+                    if best.as_ref().map_or(true, |(_, best_movements)| {
+                        movements.len() < best_movements.len()
+                    }) {
+                        best = Some((positions, movements));
+                    }
+                } else {
+                    let distance_left = self
+                        .move_board
+                        .min_moves(positions.last().expect("a walk always visits its start"), round.target());
+                    if best_partial.as_ref().map_or(true, |(_, _, best_distance)| {
+                        distance_left < *best_distance
+                    }) {
+                        best_partial = Some((positions, movements, distance_left));
+                    }
+                }
+            }
+
+            if self.elitism {
+                if let Some((positions, movements)) = &best {
+                    self.deposit(positions, movements);
+                }
+            }
+
+            if let (Some(start_time), Some(budget)) = (start_time, self.time_budget) {
+                if Local::now() - start_time >= budget {
+                    break;
+                }
+            }
+        }
+
+        // No ant reached the target within `max_steps` in any iteration before `iterations` (or
+        // `time_budget`) ran out; return the closest any ant actually got instead of fabricating a
+        // zero-move "already on target" path for a round that wasn't solved.
+        let (_, movements) = match best {
+            Some(best) => best,
+            None => match best_partial {
+                Some((_, movements, _)) => (Vec::new(), movements),
+                None => return Path::new_start_on_target(start_positions),
+            },
+        };
+
+        Path::new(
+            start_positions.clone(),
+            movements.iter().fold(start_positions, |pos, &(robot, dir)| {
+                pos.move_in_direction(round.board(), robot, dir)
+            }),
+            movements,
+        )
+    }
+}
+
+impl Default for AntColony {
+    fn default() -> Self {
+        AntColony::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use ricochet_board::{quadrant, Game, RobotPositions, Round, Symbol, Target};
+
+    use super::AntColony;
+    use crate::{Path, Solver};
+
+    fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    #[test]
+    fn board_creation() {
+        create_board();
+    }
+
+    #[test]
+    fn on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let end = start.clone();
+
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new(start.clone(), end, vec![]);
+        assert_eq!(AntColony::new().with_seed(0).solve(&round, start), expected);
+    }
+
+    #[test]
+    fn solve_reaches_target() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let path = AntColony::new()
+            .with_seed(0)
+            .with_iterations(200)
+            .with_ant_count(32)
+            .solve(&round, pos);
+
+        assert!(round.target_reached(path.end_pos()));
+    }
+
+    #[test]
+    fn elitism_still_reaches_target() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let path = AntColony::new()
+            .with_seed(0)
+            .with_iterations(200)
+            .with_ant_count(32)
+            .with_elitism(true)
+            .solve(&round, pos);
+
+        assert!(round.target_reached(path.end_pos()));
+    }
+
+    #[test]
+    fn time_budget_still_reaches_the_target() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let path = AntColony::new()
+            .with_seed(0)
+            .with_iterations(10_000)
+            .with_ant_count(32)
+            .with_time_budget(Duration::seconds(2))
+            .solve(&round, pos);
+
+        assert!(round.target_reached(path.end_pos()));
+    }
+
+    #[test]
+    fn tight_time_budget_does_not_panic_when_no_ant_lands_in_time() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        // A single-step cap makes it virtually certain no ant reaches the target before the
+        // zero-length budget cuts the loop off after the first iteration, so `best` is still
+        // `None` once `solve` decides to stop.
+        let path = AntColony::new()
+            .with_seed(0)
+            .with_iterations(10_000)
+            .with_ant_count(32)
+            .with_max_steps(1)
+            .with_time_budget(Duration::zero())
+            .solve(&round, pos.clone());
+
+        // An ant still took its one allowed step, so the fallback should report that honest
+        // partial progress rather than fabricating a zero-move "already on target" path.
+        assert_eq!(path.start_pos(), &pos);
+        assert_eq!(path.movements().len(), 1);
+        assert!(!round.target_reached(path.end_pos()));
+    }
+
+    #[test]
+    fn gives_up_without_panicking_when_no_ant_reaches_the_target() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        // A single, tiny step cap makes it virtually certain no ant reaches the target.
+        let path = AntColony::new()
+            .with_seed(0)
+            .with_iterations(1)
+            .with_ant_count(1)
+            .with_max_steps(1)
+            .solve(&round, pos.clone());
+
+        // The ant still took its one allowed step; the result should reflect that instead of
+        // claiming the start was already on target.
+        assert_eq!(path.start_pos(), &pos);
+        assert_eq!(path.movements().len(), 1);
+        assert!(!round.target_reached(path.end_pos()));
+    }
+
+    #[test]
+    fn falls_back_to_the_closest_walk_when_no_ant_reaches_the_target() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let path = AntColony::new()
+            .with_seed(0)
+            .with_iterations(3)
+            .with_ant_count(8)
+            .with_max_steps(1)
+            .solve(&round, pos.clone());
+
+        assert_eq!(path.start_pos(), &pos);
+        assert!(!path.movements().is_empty());
+        assert!(!round.target_reached(path.end_pos()));
+    }
+}
@@ -0,0 +1,209 @@
+use std::cmp::Reverse;
+
+use priority_queue::PriorityQueue;
+use ricochet_board::{RobotPositions, Round};
+
+use crate::util::{BasicVisitedNode, LeastMovesBoard, VisitedNode, VisitedNodes};
+use crate::{Path, Solver};
+
+/// How many successors [`BeamSearch`](BeamSearch) keeps alive at each depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BeamWidth {
+    /// Keep only the best `usize` successors of each layer, discarding the rest.
+    Fixed(usize),
+    /// Keep every successor, degenerating the search into plain A*.
+    Unbounded,
+}
+
+impl BeamWidth {
+    fn as_usize(self) -> usize {
+        match self {
+            BeamWidth::Fixed(width) => width,
+            BeamWidth::Unbounded => usize::MAX,
+        }
+    }
+}
+
+/// A solver using [beam search](https://en.wikipedia.org/wiki/Beam_search) to find a path to the
+/// target.
+///
+/// Like [`AStar`](crate::AStar), successors are scored by `f = g + h` with
+/// [`LeastMovesBoard`](LeastMovesBoard) as an admissible heuristic for `h`. Unlike `AStar`, only the
+/// best [`BeamWidth`](BeamWidth) successors of each depth are carried forward into the next layer;
+/// the rest are dropped even though they might have led to a shorter path. This trades the
+/// exhaustive searches' guarantee of optimality (and, on hard rounds, their blown-up memory and
+/// running time) for a search that stays fast and shallow at a fixed width.
+#[derive(Debug)]
+pub struct BeamSearch {
+    visited_nodes: VisitedNodes<BasicVisitedNode>,
+    move_board: LeastMovesBoard,
+    width: BeamWidth,
+}
+
+impl BeamSearch {
+    /// Creates a new `BeamSearch` solver keeping at most `width` successors per layer.
+    pub fn new(width: BeamWidth) -> Self {
+        Self {
+            visited_nodes: VisitedNodes::with_capacity(65536),
+            move_board: Default::default(),
+            width,
+        }
+    }
+}
+
+impl Solver for BeamSearch {
+    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+        if round.target_reached(&start_positions) {
+            return Path::new_start_on_target(start_positions);
+        }
+
+        self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
+        if self
+            .move_board
+            .is_unsolvable(&start_positions, round.target())
+        {
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+        self.visited_nodes.clear();
+
+        let width = self.width.as_usize();
+        let mut layer = vec![start_positions];
+
+        loop {
+            let mut successors = PriorityQueue::with_capacity(layer.len() * 4);
+
+            for from_pos in &layer {
+                let from_moves = self
+                    .visited_nodes
+                    .get(from_pos)
+                    .map_or(0, |node| node.moves_to_reach());
+
+                for (pos, movement) in from_pos.reachable_positions(round.board()) {
+                    let moves_from_start = from_moves + 1;
+                    let to_target = self.move_board.min_moves(&pos, round.target());
+
+                    if self
+                        .visited_nodes
+                        .add_node(
+                            pos.clone(),
+                            from_pos,
+                            moves_from_start,
+                            movement,
+                            &BasicVisitedNode::new,
+                        )
+                        .was_discarded()
+                    {
+                        continue;
+                    }
+
+                    if round.target_reached(&pos) {
+                        return self.visited_nodes.path_to(&pos);
+                    }
+
+                    successors.push(pos, BeamScore::new(moves_from_start, to_target));
+                }
+            }
+
+            if successors.is_empty() {
+                panic!(
+                    "beam search of width {:?} failed to find the target before exhausting its \
+                     frontier",
+                    self.width
+                );
+            }
+
+            layer = successors.into_sorted_vec().into_iter().take(width).collect();
+        }
+    }
+}
+
+/// Orders a beam search successor from high to low by its estimated total moves `f = g + h`, with
+/// ties broken in favor of the lower heuristic estimate `h` (i.e. the move closer to certainly
+/// needing fewer steps, rather than the one that merely got lucky so far).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct BeamScore {
+    // Reordering these fields changes the derived `Ord` and `PartialOrd` implementations.
+    total: Reverse<usize>,
+    to_target: Reverse<usize>,
+}
+
+impl BeamScore {
+    fn new(from_start: usize, to_target: usize) -> Self {
+        Self {
+            total: Reverse(from_start + to_target),
+            to_target: Reverse(to_target),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{quadrant, Game, RobotPositions, Round, Symbol, Target};
+
+    use super::{BeamSearch, BeamWidth};
+    use crate::{Path, Solver};
+
+    fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    #[test]
+    fn on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let end = start.clone();
+
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new(start.clone(), end, vec![]);
+        assert_eq!(
+            BeamSearch::new(BeamWidth::Fixed(16)).solve(&round, start),
+            expected
+        );
+    }
+
+    #[test]
+    fn unbounded_width_finds_the_optimal_path() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let optimal = crate::AStar::new().solve(&round, pos.clone());
+        let beam = BeamSearch::new(BeamWidth::Unbounded).solve(&round, pos);
+
+        assert_eq!(beam.len(), optimal.len());
+    }
+
+    #[test]
+    fn narrow_width_still_reaches_the_target() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let path = BeamSearch::new(BeamWidth::Fixed(8)).solve(&round, pos);
+        assert!(round.target_reached(path.end_pos()));
+    }
+}
@@ -0,0 +1,320 @@
+use itertools::Itertools;
+use ricochet_board::{Board, Direction, Robot, RobotPositions, Round, DIRECTIONS, ROBOTS};
+
+use crate::util::{BasicVisitedNode, RayTable, VisitedNode, VisitedNodes};
+use crate::{Path, Solver};
+
+/// Finds an optimal solution by growing a forward frontier from `start_positions` and a backward
+/// frontier from a handful of goal states in alternating layers, stopping as soon as the two meet.
+///
+/// The forward half is the same slide-graph expansion [`BreadthFirst`](crate::BreadthFirst) uses,
+/// including its direct check of whether a newly reached position satisfies the target. The
+/// backward half walks the same graph in reverse: since a move only ends where a wall or another
+/// robot stops it, a predecessor of a state is found by scanning back along the axis a robot
+/// could have slid in from, stopping the scan at the first wall or robot that would have gotten in
+/// the way. The real goal is "one particular robot on `target_position`, the other three anywhere
+/// not already occupied", which is far too large a set to seed a backward search from in full, so
+/// the backward frontier instead starts from the concrete subset obtained by moving each robot the
+/// target allows directly onto `target_position`, leaving the other three at their positions in
+/// `start_positions`; backward expansion may then move any of the four robots just as forward
+/// expansion does. That subset won't contain every goal state — in particular not ones where a
+/// blocking robot ends up away from its start cell — so the backward frontier is purely a speed-up:
+/// the forward side's own target check is what guarantees this solver is never less correct than
+/// plain `BreadthFirst`, only faster on the rounds where the two frontiers do meet.
+///
+/// Each side only ever commits to one extra move per layer, so the first meeting point or direct
+/// hit found gives the optimal path length, same as plain breadth-first search but over a frontier
+/// that grows far more slowly for deep problems.
+#[derive(Debug, Clone)]
+pub struct BidirectionalBreadthFirst {
+    forward_nodes: VisitedNodes<BasicVisitedNode>,
+    backward_nodes: VisitedNodes<BasicVisitedNode>,
+    ray_table: RayTable,
+}
+
+impl Solver for BidirectionalBreadthFirst {
+    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+        if round.target_reached(&start_positions) {
+            return Path::new_start_on_target(start_positions);
+        }
+
+        self.ray_table = RayTable::new(round.board());
+        self.forward_nodes.clear();
+        self.backward_nodes.clear();
+
+        let mut forward_frontier = vec![start_positions.clone()];
+        let mut backward_frontier: Vec<RobotPositions> = ROBOTS
+            .iter()
+            .filter(|&&robot| round.target().allows(robot.into()))
+            .map(|&robot| start_positions.with_robot_at(robot, round.target_position()))
+            .collect();
+
+        for seed in &backward_frontier {
+            self.backward_nodes.add_node(
+                seed.clone(),
+                seed,
+                0,
+                (Robot::Red, Direction::Up),
+                &BasicVisitedNode::new,
+            );
+        }
+
+        loop {
+            let mut next_forward = Vec::with_capacity(forward_frontier.len() * 4);
+            for from_pos in &forward_frontier {
+                let moves = self
+                    .forward_nodes
+                    .get(from_pos)
+                    .map_or(0, |node| node.moves_to_reach());
+
+                for (new_pos, movement) in self.ray_table.reachable_positions(from_pos) {
+                    if self
+                        .forward_nodes
+                        .add_node(new_pos.clone(), from_pos, moves + 1, movement, &BasicVisitedNode::new)
+                        .was_discarded()
+                    {
+                        continue;
+                    }
+
+                    if self.backward_nodes.get(&new_pos).is_some() {
+                        return self.stitch(&start_positions, &new_pos);
+                    }
+
+                    // The backward frontier is only seeded from a handful of plausible goal
+                    // states, not the full "target robot on target, others anywhere" predicate, so
+                    // it can miss a goal state it never happens to expand into. Checking the
+                    // predicate directly here keeps this solver as correct as plain
+                    // [`BreadthFirst`](crate::BreadthFirst): it can never do worse than a forward-
+                    // only search, and the backward frontier remains free to find a meeting point
+                    // sooner whenever it does overlap with the true optimal path.
+                    if round.target_reached(&new_pos) {
+                        let forward_path = self.forward_nodes.path_to(&new_pos);
+                        return Path::new(
+                            forward_path.start_pos().clone(),
+                            new_pos,
+                            forward_path.movements().clone(),
+                        );
+                    }
+
+                    next_forward.push(new_pos);
+                }
+            }
+            forward_frontier = next_forward;
+
+            let mut next_backward = Vec::with_capacity(backward_frontier.len() * 4);
+            for from_pos in &backward_frontier {
+                let moves = self
+                    .backward_nodes
+                    .get(from_pos)
+                    .map_or(0, |node| node.moves_to_reach());
+
+                for (pred_pos, movement) in predecessors(round.board(), from_pos) {
+                    if self
+                        .backward_nodes
+                        .add_node(pred_pos.clone(), from_pos, moves + 1, movement, &BasicVisitedNode::new)
+                        .was_discarded()
+                    {
+                        continue;
+                    }
+
+                    if pred_pos == start_positions || self.forward_nodes.get(&pred_pos).is_some() {
+                        return self.stitch(&start_positions, &pred_pos);
+                    }
+
+                    next_backward.push(pred_pos);
+                }
+            }
+            backward_frontier = next_backward;
+
+            if forward_frontier.is_empty() && backward_frontier.is_empty() {
+                panic!(
+                    "bidirectional search exhausted both frontiers without the two ever meeting"
+                );
+            }
+        }
+    }
+}
+
+impl BidirectionalBreadthFirst {
+    /// Creates a new solver which meets a forward and a backward breadth-first search in the
+    /// middle to find an optimal solution.
+    pub fn new() -> Self {
+        Self {
+            forward_nodes: VisitedNodes::with_capacity(65536),
+            backward_nodes: VisitedNodes::with_capacity(65536),
+            ray_table: Default::default(),
+        }
+    }
+
+    /// Combines the forward tree's path to `meeting` with the backward tree's path from `meeting`
+    /// to a goal state into the full solution.
+    fn stitch(&self, start_positions: &RobotPositions, meeting: &RobotPositions) -> Path {
+        let (start_pos, mut movements) = if meeting == start_positions {
+            (start_positions.clone(), Vec::new())
+        } else {
+            let forward_path = self.forward_nodes.path_to(meeting);
+            (forward_path.start_pos().clone(), forward_path.movements().clone())
+        };
+
+        let mut current = meeting.clone();
+        loop {
+            let node = self
+                .backward_nodes
+                .get(&current)
+                .expect("meeting point should have been reached by the backward search");
+
+            if node.moves_to_reach() == 0 {
+                return Path::new(start_pos, current, movements);
+            }
+
+            movements.push(node.reached_with());
+            current = node.previous_position().clone();
+        }
+    }
+}
+
+impl Default for BidirectionalBreadthFirst {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns every `RobotPositions` that could reach `positions` via one forward move, alongside the
+/// `(Robot, Direction)` of that move.
+///
+/// For each robot and direction, the robot's current field can only have been a landing spot for
+/// that direction if continuing any further is blocked by a wall or another robot; if so, every
+/// field behind it along the same axis is a valid predecessor, up until (but excluding) whatever
+/// would have stopped the robot even earlier.
+fn predecessors<'a>(
+    board: &'a Board,
+    positions: &'a RobotPositions,
+) -> impl Iterator<Item = (RobotPositions, (Robot, Direction))> + 'a {
+    ROBOTS
+        .iter()
+        .cartesian_product(DIRECTIONS.iter())
+        .flat_map(move |(&robot, &direction)| {
+            predecessors_of_move(board, positions, robot, direction)
+                .into_iter()
+                .map(move |pred| (pred, (robot, direction)))
+        })
+}
+
+/// Scans backward along the axis `robot` would have slid in from to land on its current field in
+/// `direction`, returning every field it could have started on.
+fn predecessors_of_move(
+    board: &Board,
+    positions: &RobotPositions,
+    robot: Robot,
+    direction: Direction,
+) -> Vec<RobotPositions> {
+    let landing = positions[robot];
+
+    let blocked_ahead = board.is_adjacent_to_wall(landing, direction)
+        || positions.contains_any_robot(landing.to_direction(direction, board.side_length()));
+    if !blocked_ahead {
+        return Vec::new();
+    }
+
+    let reverse = opposite(direction);
+    let mut candidates = Vec::new();
+    let mut current = landing;
+    while !board.is_adjacent_to_wall(current, reverse) {
+        let next = current.to_direction(reverse, board.side_length());
+        if positions.contains_any_robot(next) {
+            break;
+        }
+        candidates.push(positions.with_robot_at(robot, next));
+        current = next;
+    }
+    candidates
+}
+
+/// Returns the direction a robot would have to keep sliding in to undo a move in `direction`.
+fn opposite(direction: Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Down,
+        Direction::Down => Direction::Up,
+        Direction::Left => Direction::Right,
+        Direction::Right => Direction::Left,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{quadrant, Game, RobotPositions, Round, Symbol, Target};
+
+    use super::BidirectionalBreadthFirst;
+    use crate::{BreadthFirst, Path, Solver};
+
+    fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    #[test]
+    fn on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let end = start.clone();
+
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new(start.clone(), end, vec![]);
+        assert_eq!(
+            BidirectionalBreadthFirst::new().solve(&round, start),
+            expected
+        );
+    }
+
+    #[test]
+    fn matches_breadth_first_length() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let bfs_path = BreadthFirst::new().solve(&round, pos.clone());
+        let bidirectional_path = BidirectionalBreadthFirst::new().solve(&round, pos);
+
+        assert_eq!(bidirectional_path.len(), bfs_path.len());
+        assert!(round.target_reached(bidirectional_path.end_pos()));
+    }
+
+    // Regression test for a solver that silently fabricated a harder problem by requiring every
+    // non-target robot to return to its starting cell: exercised against every target on the
+    // board so that targets whose optimal solution parks a blocker away from its start cell are
+    // included, rather than panicking or returning a longer-than-optimal path.
+    #[test]
+    fn matches_breadth_first_length_for_every_target() {
+        let (pos, game) = create_board();
+
+        for (&target, &target_position) in game.targets() {
+            let round = Round::new(game.board().clone(), target, target_position);
+
+            let bfs_path = BreadthFirst::new().solve(&round, pos.clone());
+            let bidirectional_path = BidirectionalBreadthFirst::new().solve(&round, pos.clone());
+
+            assert_eq!(bidirectional_path.len(), bfs_path.len(), "target {:?}", target);
+            assert!(round.target_reached(bidirectional_path.end_pos()));
+        }
+    }
+}
@@ -1,14 +1,19 @@
 use ricochet_board::{RobotPositions, Round};
 
-use crate::util::{BasicVisitedNode, VisitedNodes};
+use crate::util::{BasicVisitedNode, RayTable, ZobristVisitedNodes};
+use crate::zobrist::PositionKey;
 use crate::{Path, Solver};
 
 /// Finds an optimal solution by visiting all possible game states in order of moves needed to
 /// reach them.
 #[derive(Debug, Clone)]
 pub struct BreadthFirst {
-    /// Manages knowledge of visited nodes.
-    visited_nodes: VisitedNodes<BasicVisitedNode>,
+    /// Manages knowledge of visited nodes, keyed by an incremental Zobrist hash instead of the
+    /// full `RobotPositions` to keep the hot loop below from rehashing a whole position on every
+    /// insert and lookup.
+    visited_nodes: ZobristVisitedNodes<BasicVisitedNode>,
+    /// Precomputed wall-stop rays for the board of the round currently being solved.
+    ray_table: RayTable,
 }
 
 impl Solver for BreadthFirst {
@@ -18,6 +23,8 @@ impl Solver for BreadthFirst {
             return Path::new(start_positions.clone(), start_positions, vec![]);
         }
 
+        self.ray_table = RayTable::new(round.board());
+        self.visited_nodes = ZobristVisitedNodes::with_capacity(round.board().side_length(), 65536);
         self.start(round, start_positions)
     }
 }
@@ -26,28 +33,35 @@ impl BreadthFirst {
     /// Create a new solver which uses a breadth first search to find an optimal solution.
     pub fn new() -> Self {
         Self {
-            visited_nodes: VisitedNodes::with_capacity(65536),
+            visited_nodes: ZobristVisitedNodes::with_capacity(16, 65536),
+            ray_table: Default::default(),
         }
     }
 
     fn start(&mut self, round: &Round, start_pos: RobotPositions) -> Path {
+        let start_hash = self.visited_nodes.hash(&start_pos);
+
         // contains all positions from which the positions in
-        let mut current_move_positions: Vec<RobotPositions> = Vec::with_capacity(16usize.pow(3));
-        current_move_positions.push(start_pos.clone());
-        let mut next_move_positions: Vec<RobotPositions> = Vec::with_capacity(16usize.pow(4));
+        let mut current_move_positions: Vec<(RobotPositions, u64)> =
+            Vec::with_capacity(16usize.pow(3));
+        current_move_positions.push((start_pos.clone(), start_hash));
+        let mut next_move_positions: Vec<(RobotPositions, u64)> =
+            Vec::with_capacity(16usize.pow(4));
 
         // Initialize the positions which will store the final position.
         let mut final_pos = start_pos;
+        let mut final_hash = start_hash;
 
         // Forward pathing to the target.
         // Computes the min. number of moves to the target and creates a tree of reachable positions
         // in `visited_nodes`, which is later used in the path creation.
         'outer: for move_n in 0.. {
-            for pos in &current_move_positions {
-                if let Some(reached) =
-                    self.eval_robot_state(round, pos, move_n, &mut next_move_positions)
+            for (pos, hash) in &current_move_positions {
+                if let Some((reached, reached_hash)) =
+                    self.eval_robot_state(round, pos, *hash, move_n, &mut next_move_positions)
                 {
                     final_pos = reached;
+                    final_hash = reached_hash;
                     break 'outer;
                 };
             }
@@ -55,29 +69,38 @@ impl BreadthFirst {
             std::mem::swap(&mut current_move_positions, &mut next_move_positions)
         }
 
-        self.visited_nodes.path_to(&final_pos)
+        self.visited_nodes.path_to(final_hash, &final_pos)
     }
 
     /// Calculates all unseen reachable positions starting from `initial_pos` and adds them to
     /// `self.visited_nodes`.
     ///
-    /// `moves` is the number of moves needed to reach `initial_pos`.
+    /// `moves` is the number of moves needed to reach `initial_pos`, `initial_hash` is its
+    /// precomputed Zobrist hash.
     /// The calculated positions are inserted into `pos_store`.
     fn eval_robot_state(
         &mut self,
         round: &Round,
         initial_pos: &RobotPositions,
+        initial_hash: u64,
         moves: usize,
-        next_positions: &mut Vec<RobotPositions>,
-    ) -> Option<RobotPositions> {
-        for (new_pos, (robot, dir)) in initial_pos.reachable_positions(round.board()) {
+        next_positions: &mut Vec<(RobotPositions, u64)>,
+    ) -> Option<(RobotPositions, u64)> {
+        for (new_pos, (robot, dir)) in self.ray_table.reachable_positions(initial_pos) {
+            let new_hash = self.visited_nodes.rehash_move(
+                initial_hash,
+                robot,
+                initial_pos[robot],
+                new_pos[robot],
+            );
+
             // Mark the new positions as visited and continue with the next one, if a better path
             // already exists.
             if self
                 .visited_nodes
                 .add_node(
-                    new_pos.clone(),
-                    &initial_pos,
+                    PositionKey::new(new_pos.clone(), new_hash),
+                    initial_pos,
                     moves + 1,
                     (robot, dir),
                     &BasicVisitedNode::new,
@@ -89,11 +112,11 @@ impl BreadthFirst {
 
             // Check if the target has been reached.
             if round.target_reached(&new_pos) {
-                return Some(new_pos);
+                return Some((new_pos, new_hash));
             }
 
             // Add new_pos to the positions to be checked
-            next_positions.push(new_pos);
+            next_positions.push((new_pos, new_hash));
         }
 
         None
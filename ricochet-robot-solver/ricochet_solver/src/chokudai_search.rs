@@ -0,0 +1,200 @@
+use std::cmp::Reverse;
+
+use priority_queue::PriorityQueue;
+use ricochet_board::{RobotPositions, Round};
+
+use crate::util::{BasicVisitedNode, LeastMovesBoard, VisitedNode, VisitedNodes};
+use crate::{Path, Solver};
+
+/// Upper bound on the number of moves a solution can need, used to size the per-depth queues.
+///
+/// No known Ricochet Robots puzzle on a reasonably sized board needs anywhere near this many
+/// moves; it only has to be a safe ceiling, not a tight one.
+const MAX_DEPTH: usize = 200;
+
+/// An anytime solver using [Chokudai search](https://qiita.com/Chokudai/items/5e7e1dc42ca13ed9c8e9)
+/// to find a good, though not necessarily optimal, path quickly.
+///
+/// Unlike [`BeamSearch`](crate::BeamSearch), which keeps a single beam and throws away everything
+/// else, Chokudai search keeps one priority queue per depth, ordered by the
+/// [`LeastMovesBoard`](LeastMovesBoard) heuristic. Each iteration pops `width` states off every
+/// depth's queue (shallowest first) and pushes their successors one depth deeper, so a solution
+/// found early doesn't stop the search: later iterations keep exploring in case they turn up a
+/// shorter one. Running for `iterations` rounds like this and keeping the shortest path seen makes
+/// it practical on the 20+ move problems where `AStar`/`IdaStar` are too slow to finish, while still
+/// returning *something* almost immediately.
+#[derive(Debug)]
+pub struct ChokudaiSearch {
+    move_board: LeastMovesBoard,
+    /// How many states are popped off each depth's queue per iteration.
+    width: usize,
+    /// How many times every depth's queue is processed before giving up.
+    iterations: usize,
+}
+
+impl ChokudaiSearch {
+    /// Creates a new `ChokudaiSearch` solver popping `width` states per depth on each of
+    /// `iterations` rounds.
+    pub fn new(width: usize, iterations: usize) -> Self {
+        Self {
+            move_board: Default::default(),
+            width,
+            iterations,
+        }
+    }
+}
+
+impl Solver for ChokudaiSearch {
+    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+        if round.target_reached(&start_positions) {
+            return Path::new_start_on_target(start_positions);
+        }
+
+        self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
+        if self
+            .move_board
+            .is_unsolvable(&start_positions, round.target())
+        {
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+
+        let mut visited_nodes: VisitedNodes<BasicVisitedNode> = VisitedNodes::with_capacity(65536);
+        let mut queues: Vec<PriorityQueue<RobotPositions, Reverse<usize>>> =
+            (0..=MAX_DEPTH).map(|_| PriorityQueue::new()).collect();
+
+        let start_h = self.move_board.min_moves(&start_positions, round.target());
+        queues[0].push(start_positions.clone(), Reverse(start_h));
+
+        let mut best: Option<RobotPositions> = None;
+
+        for _ in 0..self.iterations {
+            let mut made_progress = false;
+
+            for depth in 0..MAX_DEPTH {
+                for _ in 0..self.width {
+                    let Some((from_pos, _)) = queues[depth].pop() else {
+                        break;
+                    };
+                    made_progress = true;
+
+                    let from_moves = visited_nodes
+                        .get(&from_pos)
+                        .map_or(0, |node| node.moves_to_reach());
+
+                    for (pos, movement) in from_pos.reachable_positions(round.board()) {
+                        let moves_from_start = from_moves + 1;
+
+                        if visited_nodes
+                            .add_node(
+                                pos.clone(),
+                                &from_pos,
+                                moves_from_start,
+                                movement,
+                                &BasicVisitedNode::new,
+                            )
+                            .was_discarded()
+                        {
+                            continue;
+                        }
+
+                        if round.target_reached(&pos) {
+                            if best.as_ref().map_or(true, |shortest| {
+                                moves_from_start < visited_nodes.path_to(shortest).len()
+                            }) {
+                                best = Some(pos);
+                            }
+                        } else if moves_from_start < MAX_DEPTH {
+                            let h = self.move_board.min_moves(&pos, round.target());
+                            queues[moves_from_start].push(pos, Reverse(h));
+                        }
+                    }
+                }
+            }
+
+            if !made_progress {
+                break;
+            }
+        }
+
+        match best {
+            Some(goal) => visited_nodes.path_to(&goal),
+            None => panic!(
+                "Chokudai search with width {} exhausted its iteration budget of {} without \
+                 finding the target",
+                self.width, self.iterations
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{quadrant, Game, RobotPositions, Round, Symbol, Target};
+
+    use super::ChokudaiSearch;
+    use crate::{Path, Solver};
+
+    fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    #[test]
+    fn on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let end = start.clone();
+
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new(start.clone(), end, vec![]);
+        assert_eq!(
+            ChokudaiSearch::new(4, 32).solve(&round, start),
+            expected
+        );
+    }
+
+    #[test]
+    fn finds_the_optimal_path_given_a_generous_budget() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let optimal = crate::AStar::new().solve(&round, pos.clone());
+        let chokudai = ChokudaiSearch::new(8, 64).solve(&round, pos);
+
+        assert_eq!(chokudai.len(), optimal.len());
+    }
+
+    #[test]
+    fn narrow_budget_still_reaches_the_target() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let path = ChokudaiSearch::new(2, 16).solve(&round, pos);
+        assert!(round.target_reached(path.end_pos()));
+    }
+}
@@ -0,0 +1,240 @@
+use std::ops::RangeInclusive;
+
+use itertools::Itertools;
+use rand::{Rng, SeedableRng};
+use ricochet_board::generator::CENTER_WALLS_FROM_SIDE_LENGTH;
+use ricochet_board::{Game, PositionEncoding, RobotPositions, Round};
+
+use crate::{BreadthFirst, Path, Solver};
+
+/// The length, in moves, a generated puzzle's optimal solution must have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TargetLength {
+    /// The optimal path must be exactly this many moves long.
+    Exact(usize),
+    /// The optimal path's length must fall within this inclusive range.
+    Range(RangeInclusive<usize>),
+}
+
+impl TargetLength {
+    fn contains(&self, len: usize) -> bool {
+        match self {
+            TargetLength::Exact(exact) => len == *exact,
+            TargetLength::Range(range) => range.contains(&len),
+        }
+    }
+}
+
+/// The difficulty a generated puzzle must satisfy, see
+/// [`RoundGenerator::generate`](RoundGenerator::generate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difficulty {
+    length: TargetLength,
+    min_unique_robots: usize,
+}
+
+impl Difficulty {
+    /// Requires the optimal path to be exactly `length` moves long.
+    pub fn exact(length: usize) -> Self {
+        Self {
+            length: TargetLength::Exact(length),
+            min_unique_robots: 0,
+        }
+    }
+
+    /// Requires the optimal path's length to fall within `length`.
+    pub fn in_range(length: RangeInclusive<usize>) -> Self {
+        Self {
+            length: TargetLength::Range(length),
+            min_unique_robots: 0,
+        }
+    }
+
+    /// Additionally requires the optimal path to move at least `min_unique_robots` distinct
+    /// robots, so e.g. a one-robot shuffle isn't accepted just because it's long enough.
+    pub fn with_min_unique_robots(mut self, min_unique_robots: usize) -> Self {
+        self.min_unique_robots = min_unique_robots;
+        self
+    }
+}
+
+/// Generates playable `Round`s of a requested [`Difficulty`](Difficulty) by rejection sampling.
+///
+/// Candidates are graded the way a sudoku generator grades a puzzle: solve it and see what comes
+/// out. Each attempt draws random legal starting positions and a random target from `game`, solves
+/// the resulting `Round` with [`BreadthFirst`](crate::BreadthFirst), and accepts it once the
+/// optimal path's length and number of distinct robots moved both satisfy `difficulty`. This turns
+/// the kind of ad-hoc exploration `BreadthFirst`'s `solve_many` benchmark does into a first-class
+/// puzzle-authoring API.
+#[derive(Debug)]
+pub struct RoundGenerator {
+    rng: rand_pcg::Pcg64Mcg,
+    solver: BreadthFirst,
+}
+
+impl RoundGenerator {
+    /// Creates a new generator seeded from entropy.
+    pub fn new() -> Self {
+        Self {
+            rng: rand_pcg::Pcg64Mcg::from_entropy(),
+            solver: BreadthFirst::new(),
+        }
+    }
+
+    /// Creates a new generator deterministically seeded from `seed`, for a reproducible sequence
+    /// of generated puzzles.
+    pub fn new_seeded(seed: u128) -> Self {
+        Self {
+            rng: rand_pcg::Pcg64Mcg::new(seed),
+            solver: BreadthFirst::new(),
+        }
+    }
+
+    /// Draws random candidates from `game` until one matches `difficulty`, or gives up after
+    /// `max_attempts` rejections.
+    ///
+    /// Returns the accepted starting positions, the `Round` to be solved, and the optimal `Path`
+    /// it was graded with.
+    ///
+    /// # Panics
+    /// Panics if `game` has no targets to build a round from.
+    pub fn generate(
+        &mut self,
+        game: &Game,
+        difficulty: &Difficulty,
+        max_attempts: usize,
+    ) -> Option<(RobotPositions, Round, Path)> {
+        assert!(
+            !game.targets().is_empty(),
+            "game has no targets to generate a round for"
+        );
+
+        for _ in 0..max_attempts {
+            let start_positions = self.random_positions(game.board().side_length());
+            let target_index = self.rng.gen_range(0..game.targets().len());
+            let (&target, &target_position) = game
+                .targets()
+                .iter()
+                .nth(target_index)
+                .expect("target_index is within bounds of the non-empty target map");
+
+            let round = Round::new(game.board().clone(), target, target_position);
+            if round.target_reached(&start_positions) {
+                continue;
+            }
+
+            let path = self.solver.solve(&round, start_positions.clone());
+            if difficulty.length.contains(path.len())
+                && unique_robots(&path) >= difficulty.min_unique_robots
+            {
+                return Some((start_positions, round, path));
+            }
+        }
+
+        None
+    }
+
+    /// Draws legal starting positions, resampling as long as a robot lands inside the enclosed
+    /// center block.
+    fn random_positions(&mut self, side_length: PositionEncoding) -> RobotPositions {
+        loop {
+            let range = 0..side_length;
+            let mut next_coordinate =
+                || (self.rng.gen_range(range.clone()), self.rng.gen_range(range.clone()));
+            let positions = [
+                next_coordinate(),
+                next_coordinate(),
+                next_coordinate(),
+                next_coordinate(),
+            ];
+
+            if side_length >= CENTER_WALLS_FROM_SIDE_LENGTH {
+                let start = side_length / 2 - 1;
+                let end = start + 1;
+                if positions
+                    .iter()
+                    .any(|(c, r)| (start..=end).contains(c) && (start..=end).contains(r))
+                {
+                    continue;
+                }
+            }
+
+            return RobotPositions::from_tuples(&positions);
+        }
+    }
+}
+
+impl Default for RoundGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Counts the distinct robots moved in `path`.
+fn unique_robots(path: &Path) -> usize {
+    path.movements()
+        .iter()
+        .map(|&(robot, _)| robot)
+        .unique()
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{quadrant, Game};
+
+    use super::{Difficulty, RoundGenerator};
+
+    fn create_game() -> Game {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        Game::from_quadrants(&quadrants)
+    }
+
+    #[test]
+    fn generates_a_round_matching_the_requested_length() {
+        let game = create_game();
+        let mut generator = RoundGenerator::new_seeded(0xf00d);
+
+        let (start, round, path) = generator
+            .generate(&game, &Difficulty::in_range(1..=6), 10_000)
+            .expect("a short puzzle should be found well within the attempt budget");
+
+        assert!((1..=6).contains(&path.len()));
+        assert_eq!(path.start_pos(), &start);
+        assert!(round.target_reached(path.end_pos()));
+    }
+
+    #[test]
+    fn same_seed_generates_the_same_round() {
+        let game = create_game();
+
+        let a = RoundGenerator::new_seeded(0xf00d)
+            .generate(&game, &Difficulty::in_range(1..=6), 10_000)
+            .expect("a short puzzle should be found well within the attempt budget");
+        let b = RoundGenerator::new_seeded(0xf00d)
+            .generate(&game, &Difficulty::in_range(1..=6), 10_000)
+            .expect("a short puzzle should be found well within the attempt budget");
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let game = create_game();
+        let mut generator = RoundGenerator::new_seeded(0xf00d);
+
+        // No round on this board needs `usize::MAX` moves.
+        let result = generator.generate(&game, &Difficulty::exact(usize::MAX), 16);
+        assert!(result.is_none());
+    }
+}
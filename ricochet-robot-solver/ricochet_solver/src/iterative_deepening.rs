@@ -0,0 +1,711 @@
+use chrono::{DateTime, Duration, Local};
+use ricochet_board::{Direction, MoveOutcome, Robot, RobotPositions, Round, DIRECTIONS, ROBOTS};
+
+use crate::a_star::{AllSolutions, SolveError};
+use crate::util::LeastMovesBoard;
+use crate::{Path, Solver};
+
+/// The outcome of [`IdaStar::solve_within`](IdaStar::solve_within).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnytimeSolution {
+    /// The path is a proven shortest path: the search ruled out every shorter possibility before
+    /// the deadline.
+    Optimal(Path),
+    /// The deadline was hit before every shorter possibility could be ruled out, or
+    /// [`with_weight`](IdaStar::with_weight) was used to inflate the heuristic; the path is only
+    /// the best one found so far.
+    BestEffort(Path),
+}
+
+impl AnytimeSolution {
+    /// Returns the path, discarding whether it's proven optimal.
+    pub fn into_path(self) -> Path {
+        match self {
+            AnytimeSolution::Optimal(path) | AnytimeSolution::BestEffort(path) => path,
+        }
+    }
+
+    /// Returns `true` if the path is a proven shortest path.
+    pub fn is_optimal(&self) -> bool {
+        matches!(self, AnytimeSolution::Optimal(_))
+    }
+}
+
+/// The result of a single depth-limited DFS probe, see [`IdaStar::depth_limited_dfs`].
+enum DfsOutcome {
+    /// The target was reached; the path stack passed to the probe holds the moves to get there.
+    Found,
+    /// Every branch explored was pruned because its `f = g + h` exceeded `threshold`. Carries the
+    /// smallest such overrun, which becomes the next iteration's threshold.
+    Pruned {
+        /// The smallest `f` that exceeded `threshold` anywhere in this probe, or `usize::MAX` if
+        /// every branch was cut off some other way (already on the path) instead.
+        next_threshold: usize,
+    },
+}
+
+/// A solver using the iterative deepening (IDA* ) algorithm to find the shortest path to the
+/// target.
+///
+/// Unlike a plain breadth-first search, IDA* never keeps an open list or a map of every state it
+/// has seen: each depth-limited probe descends through [`RobotPositions`] in place via
+/// make/unmake (mirroring how chess engines push/pop moves), so memory only ever holds the current
+/// path, not the whole search tree. This makes it the solver of choice on boards too large for
+/// [`AStar`](crate::AStar)'s open list and visited-node map to fit in memory.
+// Why it's good: https://cseweb.ucsd.edu/~elkan/130/itdeep.html
+// Optimizations: https://speakerdeck.com/fogleman/ricochet-robots-solver-algorithms
+#[derive(Debug)]
+pub struct IdaStar {
+    /// This board contains the minimum number of moves to reach the target for each field.
+    ///
+    /// This minimum is a lower bound and may be impossible to reach even if all other robots are
+    /// positioned perfectly.
+    move_board: LeastMovesBoard,
+    /// Inflates the admissible heuristic used to prune branches, trading optimality for speed. See
+    /// [`with_weight`](IdaStar::with_weight).
+    weight: f64,
+    /// See [`with_timeout`](IdaStar::with_timeout). Only honored by
+    /// [`solve_all_bounded`](IdaStar::solve_all_bounded).
+    timeout: Option<Duration>,
+    /// See [`with_max_nodes`](IdaStar::with_max_nodes). Only honored by
+    /// [`solve_all_bounded`](IdaStar::solve_all_bounded).
+    max_nodes: Option<usize>,
+}
+
+impl Solver for IdaStar {
+    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+        // `with_weight` only applies to `solve_within`; search unweighted for a provably optimal
+        // path here.
+        let original_weight = self.weight;
+        self.weight = 1.0;
+        let path = self
+            .search(round, start_positions, None)
+            .expect("the deadline is `None`, so `search` always returns `Some`")
+            .into_path();
+        self.weight = original_weight;
+        path
+    }
+}
+
+impl IdaStar {
+    pub fn new() -> Self {
+        Self {
+            move_board: Default::default(),
+            weight: 1.0,
+            timeout: None,
+            max_nodes: None,
+        }
+    }
+
+    /// Inflates the admissible heuristic used to prune branches by `weight`, trading optimality for
+    /// speed.
+    ///
+    /// The search becomes bounded-suboptimal: the returned path is guaranteed to be at most a
+    /// factor `weight` longer than the optimal one. [`solve`](Solver::solve) ignores this setting
+    /// and always searches for a provably optimal path; it only affects
+    /// [`solve_within`](IdaStar::solve_within).
+    ///
+    /// # Panics
+    /// Panics if `weight` is less than `1.0`.
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        assert!(
+            weight >= 1.0,
+            "the heuristic weight has to be at least 1.0 to keep the search bounded-suboptimal"
+        );
+        self.weight = weight;
+        self
+    }
+
+    /// Stops [`solve_all_bounded`](IdaStar::solve_all_bounded) once `timeout` has elapsed since it
+    /// started, returning whatever distinct shortest paths were found so far with `truncated` set.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Stops [`solve_all_bounded`](IdaStar::solve_all_bounded) after visiting `max_nodes` states
+    /// while collecting solutions at the proven optimal length, returning whatever was found so
+    /// far with `truncated` set. Like [`with_timeout`](IdaStar::with_timeout) bounding the probes
+    /// that establish that length in the first place, this budget is only checked between states,
+    /// not while a single one is being explored, so it is a guide rather than a hard cap.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// Finds a path to the target, like [`solve`](Solver::solve), but gives up once `deadline` has
+    /// elapsed since the call started instead of running until a provably optimal path is found.
+    ///
+    /// The clock is only checked between successive threshold increases, not while a single
+    /// threshold is being searched, so a single slow iteration can still run past the deadline.
+    ///
+    /// Returns `None` if no complete path to the target was found before the deadline. Otherwise
+    /// returns [`AnytimeSolution::Optimal`](AnytimeSolution::Optimal) if every shorter bound was
+    /// ruled out first, or [`AnytimeSolution::BestEffort`](AnytimeSolution::BestEffort) if the
+    /// deadline cut the search short or [`with_weight`](IdaStar::with_weight) was used to inflate
+    /// the heuristic.
+    ///
+    /// # Panics
+    /// Panics if the round can't be solved from `start_positions`.
+    pub fn solve_within(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        deadline: Duration,
+    ) -> Option<AnytimeSolution> {
+        self.search(round, start_positions, Some(deadline))
+    }
+
+    /// Shared driver for [`solve`](Solver::solve) and [`solve_within`](IdaStar::solve_within): runs
+    /// successive depth-limited probes with an increasing `f`-cost threshold until the target is
+    /// found or `deadline` elapses.
+    ///
+    /// `deadline` of `None` searches until a provably optimal path is found, returning `Some` every
+    /// time.
+    fn search(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        deadline: Option<Duration>,
+    ) -> Option<AnytimeSolution> {
+        if round.target_reached(&start_positions) {
+            return Some(AnytimeSolution::Optimal(Path::new_start_on_target(
+                start_positions,
+            )));
+        }
+
+        self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
+        if self
+            .move_board
+            .is_unsolvable(&start_positions, round.target())
+        {
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+
+        let mut threshold = self.move_board.min_moves(&start_positions, round.target());
+        let mut positions = start_positions.clone();
+        let mut on_path = vec![start_positions.clone()];
+        let mut path = Vec::new();
+
+        let start_time = deadline.map(|_| Local::now());
+        loop {
+            match self.depth_limited_dfs(round, &mut positions, &mut on_path, &mut path, 0, threshold) {
+                DfsOutcome::Found => {
+                    let result_path = Path::new(start_positions, positions, path);
+                    return Some(if self.weight > 1.0 {
+                        AnytimeSolution::BestEffort(result_path)
+                    } else {
+                        AnytimeSolution::Optimal(result_path)
+                    });
+                }
+                DfsOutcome::Pruned { next_threshold } => threshold = next_threshold,
+            }
+
+            if let (Some(start_time), Some(deadline)) = (start_time, deadline) {
+                if Local::now() - start_time >= deadline {
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Probes every state reachable from `positions` whose `f = g + h` stays within `threshold`,
+    /// descending and backtracking in place instead of cloning `positions` at every ply.
+    ///
+    /// `positions` is mutated to "make" each candidate move before recursing and restored to
+    /// "unmake" it afterwards, so on return it always holds the same state it was called with
+    /// (unless [`DfsOutcome::Found`] is returned, in which case it holds the target state). `on_path`
+    /// and `path` are pushed and popped in lockstep with `positions`: `on_path` guards against
+    /// cycling back to a state already on the current branch, and `path` accumulates the
+    /// robot/direction moves taken so far, ready to hand to [`Path::new`] as soon as the target is
+    /// reached.
+    ///
+    /// `g` is the number of moves already taken to reach `positions`.
+    fn depth_limited_dfs(
+        &mut self,
+        round: &Round,
+        positions: &mut RobotPositions,
+        on_path: &mut Vec<RobotPositions>,
+        path: &mut Vec<(Robot, Direction)>,
+        g: usize,
+        threshold: usize,
+    ) -> DfsOutcome {
+        let mut next_threshold = usize::MAX;
+
+        for &robot in ROBOTS.iter() {
+            for &direction in DIRECTIONS.iter() {
+                let from = positions[robot];
+                let to = match positions.try_move(round.board(), robot, direction) {
+                    MoveOutcome::Moved { to, .. } => to,
+                    MoveOutcome::Blocked { .. } => continue,
+                };
+
+                // Make the move in place.
+                positions.set_robot_in_place(robot, to);
+
+                let g = g + 1;
+                let h = self.move_board.min_moves(positions, round.target());
+                let weighted_h = (h as f64 * self.weight).ceil() as usize;
+                let f = g + weighted_h;
+
+                if f > threshold {
+                    next_threshold = next_threshold.min(f);
+                } else if on_path.contains(&*positions) {
+                    // Already on this branch further up; descending again could only cycle.
+                } else if round.target_reached(positions) {
+                    path.push((robot, direction));
+                    return DfsOutcome::Found;
+                } else {
+                    on_path.push(positions.clone());
+                    path.push((robot, direction));
+
+                    match self.depth_limited_dfs(round, positions, on_path, path, g, threshold) {
+                        DfsOutcome::Found => return DfsOutcome::Found,
+                        DfsOutcome::Pruned {
+                            next_threshold: child_threshold,
+                        } => next_threshold = next_threshold.min(child_threshold),
+                    }
+
+                    on_path.pop();
+                    path.pop();
+                }
+
+                // Unmake the move.
+                positions.set_robot_in_place(robot, from);
+            }
+        }
+
+        DfsOutcome::Pruned { next_threshold }
+    }
+
+    /// Finds every distinct path of the proven minimum length, up to `max_solutions`.
+    ///
+    /// Runs the same iterative-deepening probes as [`solve`](Solver::solve) to establish the
+    /// optimal length, ignoring any configured [`weight`](IdaStar::with_weight) since a weighted
+    /// search can no longer guarantee it finds every shortest path. It then re-probes at that
+    /// final threshold without stopping at the first target reached, collecting every terminal
+    /// state reached instead: since `f = g` for any state where the target is reached, every such
+    /// state found during this last probe is reached in exactly `threshold` moves, the proven
+    /// minimum.
+    ///
+    /// # Panics
+    /// Panics if the round can't be solved from `start_positions`.
+    pub fn solve_all(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        max_solutions: usize,
+    ) -> Vec<Path> {
+        self.solve_all_impl(round, start_positions, max_solutions, false)
+            .expect("honor_budget is false, so solve_all_impl never returns an Err")
+            .paths
+    }
+
+    /// Like [`solve_all`](IdaStar::solve_all), but honors the configured
+    /// [`timeout`](IdaStar::with_timeout) and [`max_nodes`](IdaStar::with_max_nodes) instead of
+    /// running until every shortest path is proven found.
+    ///
+    /// Returns an [`AllSolutions`](AllSolutions) pairing the paths found so far with a `truncated`
+    /// flag, set if the timeout or node cap cut the search short, or if more than `max_solutions`
+    /// distinct shortest paths existed.
+    ///
+    /// # Errors
+    /// Returns [`SolveError::Unsolvable`](SolveError::Unsolvable) if the round can't be solved from
+    /// `start_positions`. Never returns [`SolveError::BudgetExceeded`](SolveError::BudgetExceeded);
+    /// a budget running out is instead reported through `AllSolutions::truncated`.
+    pub fn solve_all_bounded(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        max_solutions: usize,
+    ) -> Result<AllSolutions, SolveError> {
+        self.solve_all_impl(round, start_positions, max_solutions, true)
+    }
+
+    /// Shared driver for [`solve_all`](IdaStar::solve_all) and
+    /// [`solve_all_bounded`](IdaStar::solve_all_bounded). `honor_budget` selects whether
+    /// `self.timeout`/`self.max_nodes` are enforced and an unsolvable round is reported as
+    /// [`SolveError::Unsolvable`](SolveError::Unsolvable) rather than a panic.
+    fn solve_all_impl(
+        &mut self,
+        round: &Round,
+        start_positions: RobotPositions,
+        max_solutions: usize,
+        honor_budget: bool,
+    ) -> Result<AllSolutions, SolveError> {
+        if round.target_reached(&start_positions) {
+            return Ok(AllSolutions::new(
+                vec![Path::new_start_on_target(start_positions)],
+                false,
+            ));
+        }
+
+        self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
+        if self
+            .move_board
+            .is_unsolvable(&start_positions, round.target())
+        {
+            if honor_budget {
+                return Err(SolveError::Unsolvable);
+            }
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+
+        let deadline = if honor_budget {
+            self.timeout.map(|timeout| (Local::now(), timeout))
+        } else {
+            None
+        };
+        let max_nodes = if honor_budget { self.max_nodes } else { None };
+
+        // A weighted search is only bounded-suboptimal, so it can't be trusted to find every
+        // shortest path; enumerate as if unweighted regardless of what `with_weight` configured.
+        let original_weight = self.weight;
+        self.weight = 1.0;
+
+        // Establish the optimal path length, the same way `search` does.
+        let mut threshold = self.move_board.min_moves(&start_positions, round.target());
+        let mut positions = start_positions.clone();
+        let mut on_path = vec![start_positions.clone()];
+        let mut path = Vec::new();
+
+        loop {
+            match self.depth_limited_dfs(round, &mut positions, &mut on_path, &mut path, 0, threshold) {
+                DfsOutcome::Found => break,
+                DfsOutcome::Pruned { next_threshold } => threshold = next_threshold,
+            }
+
+            if let Some((start_time, timeout)) = deadline {
+                if Local::now() - start_time >= timeout {
+                    self.weight = original_weight;
+                    return Ok(AllSolutions::new(Vec::new(), true));
+                }
+            }
+        }
+
+        // Re-probe the proven optimal threshold, collecting every terminal state instead of
+        // stopping at the first one.
+        let mut positions = start_positions.clone();
+        let mut on_path = vec![start_positions.clone()];
+        let mut path = Vec::new();
+        let mut state = AllSolutionsState {
+            max_solutions,
+            max_nodes,
+            deadline,
+            nodes_visited: 0,
+            solutions: Vec::new(),
+            truncated: false,
+        };
+        self.collect_all_within_threshold(
+            round,
+            &mut positions,
+            &mut on_path,
+            &mut path,
+            0,
+            threshold,
+            &mut state,
+        );
+
+        self.weight = original_weight;
+
+        let paths = state
+            .solutions
+            .into_iter()
+            .map(|(end_pos, movements)| Path::new(start_positions.clone(), end_pos, movements))
+            .collect();
+
+        Ok(AllSolutions::new(paths, state.truncated))
+    }
+
+    /// Like [`depth_limited_dfs`](IdaStar::depth_limited_dfs), but never returns early: every
+    /// terminal state reached within `threshold` is recorded in `state.solutions` instead, and
+    /// every branch is explored until `state` reports a reason to stop (the `max_solutions`,
+    /// `max_nodes`, or timeout budget was exhausted).
+    fn collect_all_within_threshold(
+        &mut self,
+        round: &Round,
+        positions: &mut RobotPositions,
+        on_path: &mut Vec<RobotPositions>,
+        path: &mut Vec<(Robot, Direction)>,
+        g: usize,
+        threshold: usize,
+        state: &mut AllSolutionsState,
+    ) {
+        for &robot in ROBOTS.iter() {
+            for &direction in DIRECTIONS.iter() {
+                if state.truncated {
+                    return;
+                }
+
+                let from = positions[robot];
+                let to = match positions.try_move(round.board(), robot, direction) {
+                    MoveOutcome::Moved { to, .. } => to,
+                    MoveOutcome::Blocked { .. } => continue,
+                };
+
+                positions.set_robot_in_place(robot, to);
+                state.nodes_visited += 1;
+
+                let g = g + 1;
+                let h = self.move_board.min_moves(positions, round.target());
+                let f = g + h;
+
+                if f > threshold {
+                    // Pruned: cannot reach the target within the proven optimal length from here.
+                } else if on_path.contains(&*positions) {
+                    // Already on this branch further up; descending again could only cycle.
+                } else if round.target_reached(positions) {
+                    path.push((robot, direction));
+                    if state.solutions.len() < state.max_solutions {
+                        state.solutions.push((positions.clone(), path.clone()));
+                    } else {
+                        state.truncated = true;
+                    }
+                    path.pop();
+                } else {
+                    on_path.push(positions.clone());
+                    path.push((robot, direction));
+
+                    self.collect_all_within_threshold(
+                        round, positions, on_path, path, g, threshold, state,
+                    );
+
+                    on_path.pop();
+                    path.pop();
+                }
+
+                positions.set_robot_in_place(robot, from);
+
+                if let Some(max_nodes) = state.max_nodes {
+                    if state.nodes_visited > max_nodes {
+                        state.truncated = true;
+                    }
+                }
+                if let Some((start_time, timeout)) = state.deadline {
+                    if Local::now() - start_time >= timeout {
+                        state.truncated = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Accumulates the results of [`IdaStar::collect_all_within_threshold`]: every solution found so
+/// far and whether the `max_solutions`, `max_nodes`, or timeout budget cut the search short before
+/// every branch could be explored.
+struct AllSolutionsState {
+    max_solutions: usize,
+    max_nodes: Option<usize>,
+    deadline: Option<(DateTime<Local>, Duration)>,
+    nodes_visited: usize,
+    solutions: Vec<(RobotPositions, Vec<(Robot, Direction)>)>,
+    truncated: bool,
+}
+
+impl Default for IdaStar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use ricochet_board::{quadrant, Direction, Game, Robot, RobotPositions, Round, Symbol, Target};
+
+    use crate::{AnytimeSolution, IdaStar, Path, SolveError, Solver};
+
+    fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    #[test]
+    fn board_creation() {
+        create_board();
+    }
+
+    // Test robot already on target
+    #[test]
+    fn on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let end = start.clone();
+
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new(start.clone(), end, vec![]);
+        assert_eq!(IdaStar::new().solve(&round, start), expected);
+    }
+
+    // Test short path
+    #[test]
+    fn solve() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let expected_len = 9;
+        let path = IdaStar::new().solve(&round, pos.clone());
+        assert_eq!(path.start_pos(), &pos);
+        assert!(round.target_reached(path.end_pos()));
+        assert_eq!(path.len(), expected_len);
+    }
+
+    #[test]
+    fn solve_within_matches_solve_when_deadline_is_generous() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let expected = IdaStar::new().solve(&round, pos.clone());
+        let anytime = IdaStar::new()
+            .solve_within(&round, pos, Duration::seconds(10))
+            .expect("the round is solvable well within the deadline");
+
+        assert!(anytime.is_optimal());
+        assert_eq!(anytime.into_path(), expected);
+    }
+
+    #[test]
+    fn solve_within_reports_best_effort_when_weighted() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let anytime = IdaStar::new()
+            .with_weight(2.0)
+            .solve_within(&round, pos, Duration::seconds(10))
+            .expect("the round is solvable well within the deadline");
+
+        let path = anytime.clone().into_path();
+        assert_eq!(anytime, AnytimeSolution::BestEffort(path));
+    }
+
+    #[test]
+    fn solve_all_includes_the_single_shortest_path() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let shortest = IdaStar::new().solve(&round, pos.clone());
+        let all = IdaStar::new().solve_all(&round, pos, 10);
+
+        assert!(all.iter().all(|path| path.len() == shortest.len()));
+        assert!(all.contains(&shortest));
+    }
+
+    #[test]
+    fn solve_all_respects_max_solutions() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let all = IdaStar::new().solve_all(&round, pos, 1);
+        assert_eq!(all.len(), 1);
+    }
+
+    #[test]
+    fn solve_all_on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new_start_on_target(start.clone());
+        assert_eq!(IdaStar::new().solve_all(&round, start, 10), vec![expected]);
+    }
+
+    #[test]
+    fn solve_all_bounded_matches_solve_all_within_budget() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let all = IdaStar::new().solve_all(&round, pos.clone(), 10);
+        let bounded = IdaStar::new()
+            .with_max_nodes(1_000_000)
+            .with_timeout(Duration::seconds(10))
+            .solve_all_bounded(&round, pos, 10)
+            .expect("the round is solvable well within the budget");
+
+        assert!(!bounded.truncated());
+        assert_eq!(bounded.paths(), &all);
+    }
+
+    #[test]
+    fn solve_all_bounded_reports_unsolvable() {
+        let board = ricochet_board::Board::new_empty(2)
+            .wall_enclosure()
+            .set_vertical_line(0, 0, 1)
+            .set_horizontal_line(0, 0, 1);
+        let target_position = ricochet_board::Position::new(1, 0);
+        let round = Round::new(board, Target::Spiral, target_position);
+        let pos = RobotPositions::from_tuples(&[(0, 0), (0, 0), (0, 0), (0, 0)]);
+
+        assert_eq!(
+            IdaStar::new().solve_all_bounded(&round, pos, 10),
+            Err(SolveError::Unsolvable)
+        );
+    }
+
+    #[test]
+    fn solve_all_bounded_flags_truncation_at_max_nodes() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let bounded = IdaStar::new()
+            .with_max_nodes(1)
+            .solve_all_bounded(&round, pos, 10)
+            .expect("Unsolvable is only returned before any node is visited");
+
+        assert!(bounded.truncated());
+    }
+}
@@ -1,16 +1,32 @@
 mod a_star;
+mod ant_colony;
+mod beam_search;
+mod bidirectional_breadth_first;
 mod breadth_first;
+mod chokudai_search;
+pub mod generator;
 mod iterative_deepening;
 mod mcts;
+mod parallel_breadth_first;
+mod sequence;
 pub mod util;
+mod widening_beam_search;
+mod zobrist;
 
 use getset::Getters;
 use ricochet_board::{Direction, Robot, RobotPositions, Round};
 
-pub use a_star::AStar;
+pub use a_star::{AllSolutions, AStar, SolveError};
+pub use ant_colony::AntColony;
+pub use beam_search::{BeamSearch, BeamWidth};
+pub use bidirectional_breadth_first::BidirectionalBreadthFirst;
 pub use breadth_first::BreadthFirst;
-pub use iterative_deepening::IdaStar;
+pub use chokudai_search::ChokudaiSearch;
+pub use iterative_deepening::{AnytimeSolution, IdaStar};
 pub use mcts::Mcts;
+pub use parallel_breadth_first::ParallelBreadthFirst;
+pub use sequence::solve_sequence;
+pub use widening_beam_search::WideningBeamSearch;
 
 pub trait Solver {
     /// Find a solution to get from the `start_positions` to a target.
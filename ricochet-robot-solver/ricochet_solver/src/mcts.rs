@@ -0,0 +1,396 @@
+use std::collections::HashMap;
+
+use chrono::Local;
+use fxhash::{FxBuildHasher, FxHashSet};
+use getset::Getters;
+use rand::{Rng, SeedableRng};
+use ricochet_board::{Direction, Robot, RobotPositions, Round};
+
+use crate::{Path, Solver};
+
+type NodeMap = HashMap<RobotPositions, NodeData, FxBuildHasher>;
+
+/// How long [`Mcts`] keeps searching before committing to a move.
+///
+/// `Iterations` makes a move deterministic and reproducible given the same seed, since it always
+/// runs exactly the same number of selection/expansion/simulation/backpropagation cycles; `Time`
+/// trades that reproducibility for a wall-clock deadline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Budget {
+    /// Search for up to the given wall-clock duration per move.
+    Time(chrono::Duration),
+    /// Run exactly this many search iterations per move.
+    Iterations(usize),
+}
+
+/// Information about a visited node used in [`Mcts`].
+#[derive(Debug, Clone, Getters, PartialEq, Eq)]
+#[getset(get = "pub")]
+struct NodeData {
+    position: RobotPositions,
+    visits: usize,
+    /// The fewest moves to the target observed from this node across every backpropagation that
+    /// has passed through it, not a running average: Ricochet Robots is a deterministic
+    /// shortest-path problem, so the best continuation seen so far is a better estimate of the
+    /// true distance than the mean of all of them, which drifts with rollout noise.
+    best_score: u64,
+}
+
+impl NodeData {
+    fn new(position: RobotPositions) -> Self {
+        Self {
+            position,
+            visits: 1,
+            best_score: u64::MAX,
+        }
+    }
+
+    /// Returns all positions reachable from this node, or an empty vec if the target has been
+    /// reached.
+    fn children(&self, round: &Round) -> Vec<(RobotPositions, (Robot, Direction))> {
+        if round.target_reached(&self.position) {
+            Vec::new()
+        } else {
+            self.position.reachable_positions(round.board()).collect()
+        }
+    }
+
+    /// The best path length to the target observed from this node so far.
+    fn mean_score(&self) -> f64 {
+        self.best_score as f64
+    }
+
+    /// Records a new observed path length through this node, keeping the minimum, and adds a
+    /// visit.
+    fn update_score(&mut self, length: u64) {
+        self.visits += 1;
+        self.best_score = self.best_score.min(length);
+    }
+}
+
+/// Solver using Monte Carlo Tree Search (MCTS) with a cost-minimizing UCB1 selection rule.
+///
+/// Every child is scored by `mean_score - exploration_weight * sqrt(ln(parent_visits) / visits)`,
+/// where `mean_score` is actually the minimum path length backpropagated through that node (see
+/// [`NodeData::best_score`]); a child minimizes this score either by having a short best-known
+/// continuation or by being comparatively unexplored. Children [`Mcts`] hasn't expanded yet are
+/// treated as infinitely attractive and are always explored before any scored child is picked.
+#[derive(Debug)]
+pub struct Mcts {
+    budget: Budget,
+    exploration_weight: f64,
+    num_rollouts: usize,
+    nodes: NodeMap,
+    rng: rand_pcg::Pcg64Mcg,
+}
+
+impl Mcts {
+    /// Creates a new `Mcts` solver that searches for `budget` before each move.
+    pub fn new(budget: Budget) -> Self {
+        Self {
+            budget,
+            exploration_weight: 0.5,
+            num_rollouts: 5,
+            nodes: HashMap::with_capacity_and_hasher(65536, Default::default()),
+            rng: rand_pcg::Pcg64Mcg::from_entropy(),
+        }
+    }
+
+    /// Deterministically seeds the random policy used during simulation and random child
+    /// selection, for reproducible results.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = rand_pcg::Pcg64Mcg::seed_from_u64(seed);
+        self
+    }
+
+    /// Sets the exploration weight `c` in the UCB1 score.
+    pub fn with_exploration_weight(mut self, exploration_weight: f64) -> Self {
+        self.exploration_weight = exploration_weight;
+        self
+    }
+
+    /// Sets the number of random rollouts taken per simulation, the shortest of which is kept.
+    pub fn with_num_rollouts(mut self, num_rollouts: usize) -> Self {
+        self.num_rollouts = num_rollouts;
+        self
+    }
+
+    /// Chooses the best child to proceed with by looking at their best known scores.
+    ///
+    /// `visited` holds every position the extraction walk in [`solve`](Solver::solve) has already
+    /// stepped onto; a child already in it is only considered if every child is, since its stored
+    /// `best_score` was recorded from an earlier, different root and stepping back onto it can only
+    /// mean the walk is about to cycle rather than make progress.
+    fn choose_best_child(
+        &mut self,
+        of_node: &RobotPositions,
+        round: &Round,
+        visited: &FxHashSet<RobotPositions>,
+    ) -> (RobotPositions, (Robot, Direction)) {
+        let node_data = self.nodes.get(of_node).expect("root node must be expanded");
+        let children = node_data.children(round);
+        let unvisited: Vec<_> = children
+            .iter()
+            .filter(|(pos, _)| !visited.contains(pos))
+            .cloned()
+            .collect();
+        let candidates = if unvisited.is_empty() { &children } else { &unvisited };
+
+        let best = candidates
+            .iter()
+            .filter(|(pos, _)| self.nodes.contains_key(pos))
+            .min_by(|(a, _), (b, _)| {
+                let a = self.nodes.get(a).unwrap().mean_score();
+                let b = self.nodes.get(b).unwrap().mean_score();
+                a.partial_cmp(&b).expect("scores are never NaN")
+            });
+        match best {
+            Some(best) => best.clone(),
+            None => candidates[self.rng.gen_range(0..candidates.len())].clone(),
+        }
+    }
+
+    /// Performs the selection step and returns the path of positions walked to reach a leaf.
+    fn selection(&mut self, start: &RobotPositions, round: &Round) -> Vec<RobotPositions> {
+        let mut path = Vec::with_capacity(1024);
+        let mut visited: FxHashSet<RobotPositions> = FxHashSet::default();
+        path.push(start.clone());
+        visited.insert(start.clone());
+
+        let mut current_node = start.clone();
+        loop {
+            let node_data = match self.nodes.get(&current_node) {
+                None => break,
+                Some(data) if round.target_reached(&data.position) => break,
+                Some(data) => data,
+            };
+
+            let children = node_data.children(round);
+            let unexplored_child = children.iter().find(|child| !self.nodes.contains_key(&child.0));
+            if let Some(child) = unexplored_child {
+                current_node = child.0.clone();
+                path.push(current_node.clone());
+                visited.insert(current_node.clone());
+                continue;
+            }
+
+            let parent_visits = node_data.visits;
+            current_node = children
+                .iter()
+                .filter_map(|(pos, _)| self.nodes.get(pos))
+                .filter(|data| !visited.contains(&data.position))
+                .min_by(|a, b| {
+                    self.uct_score(a, parent_visits)
+                        .partial_cmp(&self.uct_score(b, parent_visits))
+                        .expect("scores are never NaN")
+                })
+                .map(|data| data.position.clone())
+                .expect("ran into a dead end during selection");
+
+            path.push(current_node.clone());
+            visited.insert(current_node.clone());
+        }
+
+        path
+    }
+
+    /// Performs the expansion step by inserting a new node into `self.nodes`.
+    fn expansion(&mut self, pos: &RobotPositions) {
+        if !self.nodes.contains_key(pos) {
+            self.nodes.insert(pos.clone(), NodeData::new(pos.clone()));
+        }
+    }
+
+    /// Performs the simulation step, running `num_rollouts` random rollouts to the target and
+    /// keeping the shortest.
+    fn simulation(&mut self, from: &RobotPositions, round: &Round) -> u64 {
+        let mut best = u64::MAX;
+        for _ in 0..self.num_rollouts {
+            let mut moves = 0;
+            let mut current_pos = from.clone();
+            while !round.target_reached(&current_pos) {
+                let mut reachable: Vec<_> = current_pos
+                    .reachable_positions(round.board())
+                    .map(|(pos, _)| pos)
+                    .collect();
+                let choice = self.rng.gen_range(0..reachable.len());
+                current_pos = reachable.swap_remove(choice);
+                moves += 1;
+            }
+            best = best.min(moves);
+        }
+        best
+    }
+
+    /// Backpropagates the result of a rollout, keeping the per-node *minimum* observed path
+    /// length to the target rather than a running average, so the search converges toward the
+    /// actual shortest path instead of drifting with rollout noise.
+    fn backpropagation(&mut self, path: &[RobotPositions], leaf_to_target: u64) {
+        for (i, pos) in path.iter().enumerate() {
+            let length_from_here = leaf_to_target + (path.len() - 1 - i) as u64;
+            self.nodes.get_mut(pos).unwrap().update_score(length_from_here);
+        }
+    }
+
+    /// Performs selection, expansion, simulation and backpropagation once.
+    fn run(&mut self, current_root: &RobotPositions, round: &Round) {
+        let leaf_path = self.selection(current_root, round);
+        let leaf = leaf_path.last().unwrap().clone();
+        self.expansion(&leaf);
+        let length = self.simulation(&leaf, round);
+        self.backpropagation(&leaf_path, length);
+    }
+
+    /// The cost-minimizing UCB1 score of `node_data`: its best known path length to the target,
+    /// reduced by an exploration bonus that grows with the parent's visit count and shrinks with
+    /// the node's own. Lower is more attractive, unlike the classic reward-maximizing UCB1.
+    fn uct_score(&self, node_data: &NodeData, parent_visits: usize) -> f64 {
+        let parent_visits = parent_visits.max(1);
+        node_data.mean_score()
+            - self.exploration_weight
+                * f64::sqrt(f64::ln(parent_visits as f64) / node_data.visits as f64)
+    }
+}
+
+impl Solver for Mcts {
+    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+        let mut current_pos = start_positions.clone();
+        let mut movements = Vec::new();
+        let mut visited: FxHashSet<RobotPositions> = FxHashSet::default();
+        visited.insert(current_pos.clone());
+
+        // A greedy walk that only ever looks at each node's best-known score can still cycle
+        // between a couple of positions whose scores were recorded against different, earlier
+        // roots; `choose_best_child`'s visited guard makes that unlikely, but this cap is what
+        // actually guarantees `solve` can't hang forever on a round small search budgets handle
+        // poorly.
+        let max_moves = (round.board().side_length() as usize).pow(2) * 4;
+
+        while !round.target_reached(&current_pos) {
+            if movements.len() >= max_moves {
+                panic!(
+                    "Mcts extraction did not reach the target within {} moves; increase the \
+                     search budget for this round",
+                    max_moves
+                );
+            }
+
+            match self.budget {
+                Budget::Time(duration) => {
+                    let move_start = Local::now();
+                    while Local::now() - move_start <= duration {
+                        self.run(&current_pos, round);
+                    }
+                }
+                Budget::Iterations(iterations) => {
+                    for _ in 0..iterations {
+                        self.run(&current_pos, round);
+                    }
+                }
+            }
+
+            let (new_pos, movement) = self.choose_best_child(&current_pos, round, &visited);
+            movements.push(movement);
+            current_pos = new_pos;
+            visited.insert(current_pos.clone());
+        }
+
+        Path::new(start_positions, current_pos, movements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{quadrant, Game, RobotPositions, Round, Symbol, Target};
+
+    use super::{Budget, Mcts};
+    use crate::{Path, Solver};
+
+    fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    #[test]
+    fn on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let end = start.clone();
+
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new(start.clone(), end, vec![]);
+        assert_eq!(
+            Mcts::new(Budget::Iterations(50)).with_seed(0).solve(&round, start),
+            expected
+        );
+    }
+
+    #[test]
+    fn reaches_the_target_within_an_iteration_budget() {
+        let (pos, game) = create_board();
+        let target = Target::Red(Symbol::Triangle);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let path = Mcts::new(Budget::Iterations(200))
+            .with_seed(7)
+            .solve(&round, pos);
+
+        assert!(round.target_reached(path.end_pos()));
+    }
+
+    // Regression test for a greedy extraction walk that could oscillate forever between two
+    // positions whose scores were recorded against different, earlier roots: a tiny iteration
+    // budget starves most nodes of a trustworthy score, which used to hang `solve` instead of
+    // terminating via the visited guard and move cap.
+    #[test]
+    fn does_not_hang_with_a_starved_iteration_budget() {
+        let (pos, game) = create_board();
+        let target = Target::Red(Symbol::Triangle);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let path = Mcts::new(Budget::Iterations(1)).with_seed(3).solve(&round, pos);
+
+        assert!(round.target_reached(path.end_pos()));
+    }
+
+    #[test]
+    fn same_seed_and_iteration_budget_is_reproducible() {
+        let (pos, game) = create_board();
+        let target = Target::Red(Symbol::Triangle);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let a = Mcts::new(Budget::Iterations(200))
+            .with_seed(7)
+            .solve(&round, pos.clone());
+        let b = Mcts::new(Budget::Iterations(200)).with_seed(7).solve(&round, pos);
+
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,199 @@
+use std::sync::Mutex;
+
+use dashmap::mapref::entry::Entry as DashEntry;
+use dashmap::DashMap;
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use ricochet_board::{RobotPositions, Round};
+
+use crate::util::{BasicVisitedNode, RayTable, VisitedNode};
+use crate::{Path, Solver};
+
+/// Finds an optimal solution like [`BreadthFirst`](crate::BreadthFirst), but expands each layer of
+/// the search across a rayon thread pool instead of a single thread.
+///
+/// The visited set is a [`DashMap`](DashMap) instead of `BreadthFirst`'s
+/// `HashMap<RobotPositions, _>`, so worker threads can record newly discovered positions and their
+/// parent edge concurrently. A position is only ever inserted once per layer (ties within the same
+/// layer are resolved in favor of whichever thread's insert lands first, which is fine since every
+/// candidate in a layer is reached in the same number of moves), and layers are still processed
+/// strictly in order, so the first time the target is reached is guaranteed to be with the fewest
+/// possible moves — the parallelism only speeds up expanding a layer, it never changes which layer
+/// the target is first found in.
+#[derive(Debug)]
+pub struct ParallelBreadthFirst {
+    ray_table: RayTable,
+    threads: usize,
+}
+
+impl ParallelBreadthFirst {
+    /// Creates a new solver that expands each BFS layer across `threads` worker threads.
+    pub fn new(threads: usize) -> Self {
+        Self {
+            ray_table: Default::default(),
+            threads,
+        }
+    }
+
+    fn start(&self, round: &Round, start_pos: RobotPositions) -> Path {
+        let visited: DashMap<RobotPositions, BasicVisitedNode> = DashMap::new();
+        let found: Mutex<Option<RobotPositions>> = Mutex::new(None);
+
+        let mut current_layer = vec![start_pos];
+
+        for move_n in 1.. {
+            let next_layer: Vec<RobotPositions> = current_layer
+                .par_iter()
+                .flat_map_iter(|pos| {
+                    let pos = pos.clone();
+                    self.ray_table
+                        .reachable_positions(&pos)
+                        .collect::<Vec<_>>()
+                        .into_iter()
+                        .map(move |(new_pos, movement)| (pos.clone(), new_pos, movement))
+                })
+                .filter_map(|(from_pos, new_pos, movement)| {
+                    let inserted = match visited.entry(new_pos.clone()) {
+                        DashEntry::Occupied(_) => false,
+                        DashEntry::Vacant(vacant) => {
+                            vacant.insert(BasicVisitedNode::new(move_n, from_pos, movement));
+                            true
+                        }
+                    };
+
+                    if !inserted {
+                        return None;
+                    }
+
+                    if round.target_reached(&new_pos) {
+                        let mut found = found.lock().unwrap();
+                        if found.is_none() {
+                            *found = Some(new_pos.clone());
+                        }
+                    }
+
+                    Some(new_pos)
+                })
+                .collect();
+
+            if let Some(goal) = found.lock().unwrap().take() {
+                return path_to(&visited, &goal);
+            }
+
+            if next_layer.is_empty() {
+                panic!(
+                    "parallel breadth-first search exhausted every reachable state without \
+                     finding the target"
+                );
+            }
+            current_layer = next_layer;
+        }
+
+        unreachable!("the loop above only exits through its own return or panic")
+    }
+}
+
+impl Solver for ParallelBreadthFirst {
+    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+        if round.target_reached(&start_positions) {
+            return Path::new_start_on_target(start_positions);
+        }
+
+        self.ray_table = RayTable::new(round.board());
+
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(self.threads)
+            .build()
+            .expect("failed to build the rayon thread pool");
+
+        pool.install(|| self.start(round, start_positions))
+    }
+}
+
+/// Reconstructs the shortest known path to `positions` by following parent links stored in
+/// `visited`, mirroring [`VisitedNodes::path_to`](crate::util::VisitedNodes::path_to).
+///
+/// # Panics
+/// Panics if `positions` has yet to be visited.
+fn path_to(visited: &DashMap<RobotPositions, BasicVisitedNode>, positions: &RobotPositions) -> Path {
+    let mut path = Vec::with_capacity(32);
+    let mut current_pos = positions.clone();
+
+    loop {
+        let (reached_with, previous_pos, moves_to_reach) = {
+            let node = visited
+                .get(&current_pos)
+                .expect("Failed to find a supposed source position");
+            (
+                node.reached_with(),
+                node.previous_position().clone(),
+                node.moves_to_reach(),
+            )
+        };
+
+        path.push(reached_with);
+        current_pos = previous_pos;
+        if moves_to_reach == 1 {
+            break;
+        }
+    }
+
+    path.reverse();
+    Path::new(current_pos, positions.clone(), path)
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{quadrant, Game, RobotPositions, Round, Symbol, Target};
+
+    use super::ParallelBreadthFirst;
+    use crate::{Path, Solver};
+
+    fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    #[test]
+    fn on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let end = start.clone();
+
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new(start.clone(), end, vec![]);
+        assert_eq!(ParallelBreadthFirst::new(2).solve(&round, start), expected);
+    }
+
+    #[test]
+    fn matches_sequential_breadth_first_length() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let sequential = crate::BreadthFirst::new().solve(&round, pos.clone());
+        let parallel = ParallelBreadthFirst::new(4).solve(&round, pos);
+
+        assert_eq!(parallel.len(), sequential.len());
+        assert!(round.target_reached(parallel.end_pos()));
+    }
+}
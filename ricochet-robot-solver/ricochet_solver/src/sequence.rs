@@ -0,0 +1,131 @@
+use itertools::Itertools;
+use ricochet_board::{RobotPositions, Round};
+
+use crate::{Path, Solver};
+
+/// Solves a sequence of targets in the order that minimizes the total number of moves, by chaining
+/// single-target solves through every permutation of `rounds` and keeping the cheapest one.
+///
+/// Every `Round` in `rounds` is expected to share the same board and differ only in its target;
+/// within one ordering, the end positions of one leg become the start positions of the next, and
+/// `solver` picks each leg's path exactly as it would for a standalone [`Solver::solve`] call (so
+/// it keeps building its own per-target heuristic, e.g. a fresh `LeastMovesBoard`, for every leg).
+/// The returned `Vec<Path>` holds one leg per visited target, in visiting order.
+///
+/// This evaluates `rounds.len()!` orderings, so it's only practical for a handful of targets.
+///
+/// # Panics
+/// Panics if `rounds` is empty.
+pub fn solve_sequence<S: Solver>(
+    solver: &mut S,
+    rounds: &[Round],
+    start_positions: RobotPositions,
+) -> Vec<Path> {
+    assert!(
+        !rounds.is_empty(),
+        "need at least one round to build a sequence from"
+    );
+
+    (0..rounds.len())
+        .permutations(rounds.len())
+        .map(|order| {
+            let mut positions = start_positions.clone();
+            order
+                .into_iter()
+                .map(|i| {
+                    let leg = solver.solve(&rounds[i], positions.clone());
+                    positions = leg.end_pos().clone();
+                    leg
+                })
+                .collect::<Vec<Path>>()
+        })
+        .min_by_key(|legs| legs.iter().map(Path::len).sum::<usize>())
+        .expect("at least one ordering exists since rounds is non-empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{quadrant, Game, RobotPositions, Round, Symbol, Target};
+
+    use super::solve_sequence;
+    use crate::{BreadthFirst, Path, Solver};
+
+    fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    #[test]
+    fn visits_every_target_in_order() {
+        let (pos, game) = create_board();
+        let targets = [
+            Target::Yellow(Symbol::Hexagon),
+            Target::Green(Symbol::Triangle),
+        ];
+
+        let rounds: Vec<Round> = targets
+            .iter()
+            .map(|&target| {
+                Round::new(
+                    game.board().clone(),
+                    target,
+                    game.get_target_position(&target).unwrap(),
+                )
+            })
+            .collect();
+
+        let legs = solve_sequence(&mut BreadthFirst::new(), &rounds, pos);
+
+        assert_eq!(legs.len(), rounds.len());
+        let mut visited_positions = legs[0].start_pos().clone();
+        for (round, leg) in rounds_in_visit_order(&rounds, &legs).zip(&legs) {
+            assert_eq!(*leg.start_pos(), visited_positions);
+            assert!(round.target_reached(leg.end_pos()));
+            visited_positions = leg.end_pos().clone();
+        }
+    }
+
+    /// Figures out, for each leg, which of `rounds` it solved, by matching reached targets.
+    fn rounds_in_visit_order<'a>(
+        rounds: &'a [Round],
+        legs: &[Path],
+    ) -> impl Iterator<Item = &'a Round> {
+        legs.iter()
+            .map(|leg| {
+                rounds
+                    .iter()
+                    .find(|round| round.target_reached(leg.end_pos()))
+                    .expect("every leg should reach one of the configured targets")
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn single_target_matches_solve() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let mut solver = BreadthFirst::new();
+        let direct = solver.solve(&round, pos.clone());
+        let legs = solve_sequence(&mut solver, &[round], pos);
+
+        assert_eq!(legs, vec![direct]);
+    }
+}
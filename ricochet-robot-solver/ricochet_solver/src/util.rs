@@ -1,13 +1,118 @@
 use fxhash::FxHashMap;
+use itertools::Itertools;
 use ricochet_board::{
     Board, Direction, Position, PositionEncoding, Robot, RobotPositions, Target, DIRECTIONS, ROBOTS,
 };
 use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::ops;
 
+use crate::zobrist::{PassthroughBuildHasher, PositionKey, ZobristTable, ZOBRIST_SEED};
 use crate::Path;
 
+/// Precomputes, for every cell and direction on a fixed `Board`, the ordered list of cells a robot
+/// standing there would cross while sliding that way before a wall stops it.
+///
+/// This mirrors the magic-bitboard idea used by chess move generators: doing the wall analysis once
+/// per board turns the BFS/A* successor loops, which used to re-walk
+/// `is_adjacent_to_wall`/`to_direction` cell by cell on every expansion, into indexing a
+/// precomputed `Vec` instead. It only knows about walls, not other robots, so a lookup
+/// still has to be combined with a scan for the first occupied cell, see
+/// [`slide_stop`](Self::slide_stop).
+///
+/// Has to be rebuilt whenever the underlying board's walls change; the per-expansion
+/// `Board`-walking path used to build it remains available as a fallback through
+/// [`Board::is_adjacent_to_wall`](Board::is_adjacent_to_wall) directly.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RayTable {
+    side_length: usize,
+    rays: Vec<[Vec<Position>; 4]>,
+}
+
+impl RayTable {
+    /// Precomputes every ray on `board`.
+    ///
+    /// Relatively expensive compared to a single lookup; meant to be built once per board and
+    /// reused across every successor expansion of a search.
+    pub fn new(board: &Board) -> Self {
+        let side_length = board.side_length() as usize;
+        let mut rays = vec![<[Vec<Position>; 4]>::default(); side_length * side_length];
+
+        for column in 0..side_length as PositionEncoding {
+            for row in 0..side_length as PositionEncoding {
+                let pos = Position::new(column, row);
+                for (dir_index, &direction) in DIRECTIONS.iter().enumerate() {
+                    rays[Self::index(pos, side_length)][dir_index] =
+                        Self::cast_ray(board, pos, direction);
+                }
+            }
+        }
+
+        Self { side_length, rays }
+    }
+
+    /// Walks from `pos` towards `direction` one cell at a time until a wall stops it, collecting
+    /// every cell crossed along the way (the wall-stop square is the last one).
+    fn cast_ray(board: &Board, mut pos: Position, direction: Direction) -> Vec<Position> {
+        let mut ray = Vec::new();
+        while !board.is_adjacent_to_wall(pos, direction) {
+            pos = pos.to_direction(direction, board.side_length());
+            ray.push(pos);
+        }
+        ray
+    }
+
+    fn index(pos: Position, side_length: usize) -> usize {
+        pos.column() as usize * side_length + pos.row() as usize
+    }
+
+    /// Returns the precomputed ray of cells crossed sliding from `pos` towards `direction`, ending
+    /// at (and including) the wall-stop square; empty if a wall sits right next to `pos`.
+    pub fn ray(&self, pos: Position, direction: Direction) -> &[Position] {
+        let dir_index = DIRECTIONS
+            .iter()
+            .position(|&dir| dir == direction)
+            .expect("DIRECTIONS covers every Direction");
+        &self.rays[Self::index(pos, self.side_length)][dir_index]
+    }
+
+    /// Finds where `robot` stops sliding in `direction` from `positions`, using the precomputed ray
+    /// instead of re-walking the board for the wall check.
+    ///
+    /// Returns `None` if `robot` can't move at all: a wall sits right next to it, or the very next
+    /// cell on the ray is already occupied by another robot.
+    pub fn slide_stop(
+        &self,
+        positions: &RobotPositions,
+        robot: Robot,
+        direction: Direction,
+    ) -> Option<Position> {
+        self.ray(positions[robot], direction)
+            .iter()
+            .take_while(|&&cell| !positions.contains_any_robot(cell))
+            .last()
+            .copied()
+    }
+
+    /// Creates an iterator over all positions reachable in one move from `positions`, like
+    /// [`RobotPositions::reachable_positions`](RobotPositions::reachable_positions) but looking up
+    /// precomputed rays instead of walking the board cell by cell for every candidate move.
+    pub fn reachable_positions<'a>(
+        &'a self,
+        positions: &RobotPositions,
+    ) -> impl Iterator<Item = (RobotPositions, (Robot, Direction))> + 'a {
+        let initial = positions.clone();
+        ROBOTS
+            .iter()
+            .cartesian_product(DIRECTIONS.iter())
+            .filter_map(move |(&robot, &direction)| {
+                self.slide_stop(&initial, robot, direction)
+                    .map(|to| (initial.with_robot_at(robot, to), (robot, direction)))
+            })
+    }
+}
+
 /// The possible outcomes when trying to add a node to [`VisitedNodes`](VisitedNodes).
 pub(crate) enum AddNodeOutcome {
     /// The added node was previously unknown and has been added.
@@ -124,6 +229,108 @@ impl<N: VisitedNode> VisitedNodes<N> {
     }
 }
 
+/// Like [`VisitedNodes`](VisitedNodes), but keyed by an incremental Zobrist hash
+/// ([`PositionKey`](PositionKey)) instead of the full `RobotPositions`, for the hot loops of
+/// solvers where structurally hashing every successor dominates runtime.
+///
+/// A hash collision can never be mistaken for the same state: `PositionKey`'s `Hash` impl only
+/// ever feeds the map its precomputed `u64`, but its `Eq` impl still falls back to comparing the
+/// full `RobotPositions`, so two different states that happen to collide are kept as distinct
+/// entries rather than clobbering one another.
+#[derive(Debug, Clone)]
+pub(crate) struct ZobristVisitedNodes<N: VisitedNode> {
+    table: ZobristTable,
+    nodes: HashMap<PositionKey, N, PassthroughBuildHasher>,
+}
+
+impl<N: VisitedNode> ZobristVisitedNodes<N> {
+    /// Creates a new `ZobristVisitedNodes` for a board of `side_length`, with the given `capacity`.
+    ///
+    /// The backing `ZobristTable` is seeded from the fixed [`ZOBRIST_SEED`](ZOBRIST_SEED) rather
+    /// than entropy, so hashes of the same position are reproducible across runs.
+    pub fn with_capacity(side_length: PositionEncoding, capacity: usize) -> Self {
+        Self {
+            table: ZobristTable::from_seed(ZOBRIST_SEED, side_length),
+            nodes: HashMap::with_capacity_and_hasher(capacity, Default::default()),
+        }
+    }
+
+    /// Removes all stored nodes.
+    pub fn clear(&mut self) {
+        self.nodes.clear()
+    }
+
+    /// Computes the hash of `positions` from scratch.
+    pub fn hash(&self, positions: &RobotPositions) -> u64 {
+        self.table.hash(positions)
+    }
+
+    /// Derives the hash of the position reached by moving `robot` from `from` to `to`, given the
+    /// hash of the position it was moved from.
+    pub fn rehash_move(&self, hash: u64, robot: Robot, from: Position, to: Position) -> u64 {
+        self.table.rehash_move(hash, robot, from, to)
+    }
+
+    /// Adds a node at `key`, following the same discard rules as
+    /// [`VisitedNodes::add_node`](VisitedNodes::add_node).
+    pub fn add_node<F>(
+        &mut self,
+        key: PositionKey,
+        from: &RobotPositions,
+        moves: usize,
+        moved: (Robot, Direction),
+        create_node: &F,
+    ) -> AddNodeOutcome
+    where
+        F: Fn(usize, RobotPositions, (Robot, Direction)) -> N,
+    {
+        match self.nodes.entry(key) {
+            Entry::Occupied(occupied) if occupied.get().moves_to_reach() <= moves => {
+                AddNodeOutcome::BetterKnown
+            }
+            Entry::Occupied(mut occupied) => {
+                let visited = create_node(moves, from.clone(), moved);
+                occupied.insert(visited);
+                AddNodeOutcome::WorseKnown
+            }
+            Entry::Vacant(vacant) => {
+                let visited = create_node(moves, from.clone(), moved);
+                vacant.insert(visited);
+                AddNodeOutcome::New
+            }
+        }
+    }
+
+    /// Returns the shortest known path to `positions`, which hashes to `hash`.
+    ///
+    /// # Panics
+    /// Panics if `positions` has yet to be visited.
+    pub fn path_to(&self, hash: u64, positions: &RobotPositions) -> Path {
+        let mut path = Vec::with_capacity(32);
+        let mut current_hash = hash;
+        let mut current_pos = positions.clone();
+
+        loop {
+            let key = PositionKey::new(current_pos.clone(), current_hash);
+            let current_node = self
+                .nodes
+                .get(&key)
+                .expect("Failed to find a supposed source position");
+            path.push(current_node.reached_with());
+            let previous_pos = current_node.previous_position().clone();
+            if current_node.moves_to_reach() == 1 {
+                current_pos = previous_pos;
+                break;
+            }
+            current_hash = self.table.hash(&previous_pos);
+            current_pos = previous_pos;
+        }
+
+        path.reverse();
+        Path::new(current_pos, positions.clone(), path)
+    }
+}
+
 /// Defines the functionality and information a visited node has to provide.
 ///
 /// This makes it possible to have differently optimized implementations depending on the algorithm.
@@ -200,49 +407,26 @@ impl LeastMovesBoard {
     /// Creates a new board and calculates the minimum number of moves needed to reach the target
     /// from each field.
     ///
-    /// The board is created by starting from the target position and going through all fields from
-    /// which the target can be reached in one move. These fields are assigned a lower bound of 1
-    /// and are added to the list of next positons to be expanded. This repeats until only a subset
-    /// of the positions from which the target can never be reached are left. Those positions are
-    /// marked with a lower bound of `board.side_length().pow(2)`, a bound longer than possible on a
-    /// square board.
+    /// Delegates the actual reverse flood-fill to
+    /// [`Board::move_lower_bounds`](Board::move_lower_bounds) and reshapes its flat, `u8`-capped
+    /// result into the `column`-major `Vec<Vec<usize>>` the rest of this type indexes into;
+    /// unreachable cells are remapped from `u8::MAX` to `board.side_length().pow(2)`, a bound
+    /// longer than possible on a square board, which is what [`is_unsolvable`](Self::is_unsolvable)
+    /// checks against.
     pub fn new(board: &Board, target_position: Position) -> Self {
         let len = board.side_length() as usize;
-        let mut move_board = vec![vec![len * len; len]; len];
-
-        let mut current_moves = Vec::with_capacity(256);
-        let mut next_moves = current_moves.clone();
-
-        move_board[target_position.column() as usize][target_position.row() as usize] = 0;
-        current_moves.push(target_position);
-
-        for move_n in 1usize.. {
-            for &pos in &current_moves {
-                for &dir in DIRECTIONS.iter() {
-                    // Start from pos for each direction.
-                    let mut check_pos = pos;
-                    loop {
-                        if board.is_adjacent_to_wall(check_pos, dir) {
-                            break;
-                        }
-                        check_pos = check_pos.to_direction(dir, len as PositionEncoding);
-                        let current_min =
-                            &mut move_board[check_pos.column() as usize][check_pos.row() as usize];
-                        if move_n < *current_min {
-                            // new position found
-                            *current_min = move_n;
-                            next_moves.push(check_pos);
-                        }
-                    }
-                }
-            }
-
-            if next_moves.is_empty() {
-                break;
-            }
-            current_moves.clear();
-            std::mem::swap(&mut current_moves, &mut next_moves);
-        }
+        let bounds = board.move_lower_bounds(target_position);
+
+        let move_board = (0..len)
+            .map(|column| {
+                (0..len)
+                    .map(|row| match bounds[column * len + row] {
+                        u8::MAX => len * len,
+                        bound => bound as usize,
+                    })
+                    .collect()
+            })
+            .collect();
 
         Self {
             board: move_board,
@@ -284,11 +468,141 @@ impl ops::Index<Position> for LeastMovesBoard {
     }
 }
 
+/// Maps every `RobotPositions` state reachable from a starting configuration to the minimum number
+/// of moves needed to reach it.
+///
+/// Unlike [`LeastMovesBoard`](LeastMovesBoard), which only tracks how far a single sliding robot is
+/// from a fixed target, `ReachabilityMap` runs the same layered expansion [`BreadthFirst`]
+/// (crate::BreadthFirst) uses over the full four-robot state space and keeps every state it visits,
+/// so it can answer questions about the whole board instead of one target: how far the hardest
+/// reachable state is, how many states sit at a given depth, and so on. Building it is exhaustive,
+/// so it's meant for offline analysis (e.g. picking the hardest target on a board, or grading how
+/// "open" a board's layout is) rather than for use inside a solver's hot loop.
+#[derive(Debug, Clone)]
+pub struct ReachabilityMap {
+    distances: FxHashMap<RobotPositions, usize>,
+    max_depth: usize,
+}
+
+impl ReachabilityMap {
+    /// Explores every state reachable from `start_positions` on `board`, recording the minimum
+    /// number of moves needed to reach each one.
+    pub fn new(board: &Board, start_positions: RobotPositions) -> Self {
+        let ray_table = RayTable::new(board);
+
+        let mut distances = FxHashMap::default();
+        distances.insert(start_positions.clone(), 0);
+
+        let mut frontier = vec![start_positions];
+        let mut max_depth = 0;
+
+        loop {
+            let mut next_frontier = Vec::with_capacity(frontier.len() * 4);
+            for pos in &frontier {
+                for (new_pos, _) in ray_table.reachable_positions(pos) {
+                    if let Entry::Vacant(vacant) = distances.entry(new_pos.clone()) {
+                        vacant.insert(max_depth + 1);
+                        next_frontier.push(new_pos);
+                    }
+                }
+            }
+
+            if next_frontier.is_empty() {
+                break;
+            }
+            max_depth += 1;
+            frontier = next_frontier;
+        }
+
+        Self {
+            distances,
+            max_depth,
+        }
+    }
+
+    /// Returns the minimum number of moves needed to reach `positions`, or `None` if it was never
+    /// visited while building the map.
+    pub fn distance_to(&self, positions: &RobotPositions) -> Option<usize> {
+        self.distances.get(positions).copied()
+    }
+
+    /// Returns every state known to be reachable in exactly `depth` moves.
+    pub fn states_at_depth(&self, depth: usize) -> impl Iterator<Item = &RobotPositions> {
+        self.distances
+            .iter()
+            .filter(move |&(_, &dist)| dist == depth)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Returns the largest minimum distance found to any reachable state, i.e. the depth of the
+    /// hardest-to-reach state explored from the starting positions.
+    pub fn farthest(&self) -> usize {
+        self.max_depth
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use ricochet_board::{Board, Position, PositionEncoding, RobotPositions, Target};
+    use ricochet_board::{Board, Direction, Position, PositionEncoding, Robot, RobotPositions, Target};
+
+    use super::{LeastMovesBoard, RayTable, ReachabilityMap};
+
+    #[test]
+    fn ray_stops_at_the_wall() {
+        let board = Board::new_empty(4).wall_enclosure();
+        let ray_table = RayTable::new(&board);
+
+        assert_eq!(
+            ray_table.ray(Position::new(0, 0), Direction::Right),
+            &[
+                Position::new(1, 0),
+                Position::new(2, 0),
+                Position::new(3, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn slide_stop_matches_try_move() {
+        let board = Board::new_empty(4).wall_enclosure();
+        let ray_table = RayTable::new(&board);
+        let positions = RobotPositions::from_tuples(&[(0, 0), (3, 0), (0, 1), (0, 2)]);
 
-    use super::LeastMovesBoard;
+        assert_eq!(
+            ray_table.slide_stop(&positions, Robot::Red, Direction::Right),
+            Some(Position::new(2, 0))
+        );
+    }
+
+    #[test]
+    fn slide_stop_is_none_when_immediately_blocked() {
+        let board = Board::new_empty(4).wall_enclosure();
+        let ray_table = RayTable::new(&board);
+        let positions = RobotPositions::from_tuples(&[(0, 0), (1, 0), (0, 1), (0, 2)]);
+
+        assert_eq!(
+            ray_table.slide_stop(&positions, Robot::Red, Direction::Right),
+            None
+        );
+    }
+
+    #[test]
+    fn reachable_positions_matches_the_board_walking_version() {
+        let board = Board::new_empty(4).wall_enclosure();
+        let ray_table = RayTable::new(&board);
+        let positions = RobotPositions::from_tuples(&[(0, 0), (3, 0), (0, 1), (0, 2)]);
+
+        let mut via_table = ray_table
+            .reachable_positions(&positions)
+            .collect::<Vec<_>>();
+        let mut via_board = positions
+            .reachable_positions(&board)
+            .collect::<Vec<_>>();
+        via_table.sort_by_key(|(_, (robot, dir))| (format!("{:?}", robot), *dir));
+        via_board.sort_by_key(|(_, (robot, dir))| (format!("{:?}", robot), *dir));
+
+        assert_eq!(via_table, via_board);
+    }
 
     #[test]
     fn empty_move_board() {
@@ -341,4 +655,34 @@ mod tests {
         assert_eq!(move_board.min_moves(&rob_pos, Target::Spiral), 4);
         assert_eq!(move_board.is_unsolvable(&rob_pos, Target::Spiral), true);
     }
+
+    #[test]
+    fn reachability_map_only_finds_the_start_when_fully_enclosed() {
+        let board = Board::new_empty(2)
+            .wall_enclosure()
+            .set_vertical_line(0, 0, 1)
+            .set_horizontal_line(0, 0, 1);
+        let start = RobotPositions::from_tuples(&[(0, 0), (0, 0), (0, 0), (0, 0)]);
+
+        let map = ReachabilityMap::new(&board, start.clone());
+
+        assert_eq!(map.distance_to(&start), Some(0));
+        assert_eq!(map.farthest(), 0);
+        assert_eq!(map.states_at_depth(0).collect::<Vec<_>>(), vec![&start]);
+    }
+
+    #[test]
+    fn reachability_map_finds_states_one_slide_away() {
+        let board = Board::new_empty(4).wall_enclosure();
+        let start = RobotPositions::from_tuples(&[(0, 0), (3, 3), (3, 0), (0, 3)]);
+
+        let map = ReachabilityMap::new(&board, start.clone());
+
+        assert_eq!(map.distance_to(&start), Some(0));
+        assert!(map.farthest() >= 1);
+        assert!(map.states_at_depth(1).count() > 0);
+        for pos in map.states_at_depth(1) {
+            assert_eq!(map.distance_to(pos), Some(1));
+        }
+    }
 }
@@ -0,0 +1,214 @@
+use std::cmp::Reverse;
+
+use priority_queue::PriorityQueue;
+use ricochet_board::{RobotPositions, Round};
+
+use crate::util::{BasicVisitedNode, LeastMovesBoard, VisitedNode, VisitedNodes};
+use crate::{Path, Solver};
+
+/// A solver using [beam search](https://en.wikipedia.org/wiki/Beam_search) with iterative
+/// widening, so that a frontier too narrow to reach the target doesn't leave the search with
+/// nothing to show for it.
+///
+/// Scoring and layer expansion work exactly like [`BeamSearch`](crate::BeamSearch): successors are
+/// scored by `f = g + h` with [`LeastMovesBoard`](LeastMovesBoard) as the heuristic, and only the
+/// best `width` of them survive into the next layer. The difference is what happens when a layer's
+/// successors run out before the target is reached: instead of giving up, the search restarts from
+/// scratch with the width doubled (up to `max_width`), trading the wasted earlier work for a better
+/// chance of terminating. This keeps the solver correct (it eventually explores a wide enough beam
+/// to succeed, up to the cap) while still usually finishing far faster than an exhaustive search on
+/// rounds with long solutions.
+#[derive(Debug)]
+pub struct WideningBeamSearch {
+    visited_nodes: VisitedNodes<BasicVisitedNode>,
+    move_board: LeastMovesBoard,
+    initial_width: usize,
+    max_width: usize,
+}
+
+impl WideningBeamSearch {
+    /// Creates a new `WideningBeamSearch` starting at `initial_width` and doubling on failure up
+    /// to `max_width`.
+    pub fn new(initial_width: usize, max_width: usize) -> Self {
+        Self {
+            visited_nodes: VisitedNodes::with_capacity(65536),
+            move_board: Default::default(),
+            initial_width,
+            max_width,
+        }
+    }
+
+    /// Runs a single beam search pass at a fixed `width`, returning `None` if the frontier is
+    /// exhausted before the target is reached.
+    fn solve_with_width(&mut self, round: &Round, start_positions: &RobotPositions, width: usize) -> Option<Path> {
+        self.visited_nodes.clear();
+        let mut layer = vec![start_positions.clone()];
+
+        loop {
+            let mut successors = PriorityQueue::with_capacity(layer.len() * 4);
+
+            for from_pos in &layer {
+                let from_moves = self
+                    .visited_nodes
+                    .get(from_pos)
+                    .map_or(0, |node| node.moves_to_reach());
+
+                for (pos, movement) in from_pos.reachable_positions(round.board()) {
+                    let moves_from_start = from_moves + 1;
+                    let to_target = self.move_board.min_moves(&pos, round.target());
+
+                    if self
+                        .visited_nodes
+                        .add_node(
+                            pos.clone(),
+                            from_pos,
+                            moves_from_start,
+                            movement,
+                            &BasicVisitedNode::new,
+                        )
+                        .was_discarded()
+                    {
+                        continue;
+                    }
+
+                    if round.target_reached(&pos) {
+                        return Some(self.visited_nodes.path_to(&pos));
+                    }
+
+                    successors.push(pos, WideningBeamScore::new(moves_from_start, to_target));
+                }
+            }
+
+            if successors.is_empty() {
+                return None;
+            }
+
+            layer = successors
+                .into_sorted_vec()
+                .into_iter()
+                .take(width)
+                .collect();
+        }
+    }
+}
+
+impl Solver for WideningBeamSearch {
+    fn solve(&mut self, round: &Round, start_positions: RobotPositions) -> Path {
+        if round.target_reached(&start_positions) {
+            return Path::new_start_on_target(start_positions);
+        }
+
+        self.move_board = LeastMovesBoard::new(round.board(), round.target_position());
+        if self
+            .move_board
+            .is_unsolvable(&start_positions, round.target())
+        {
+            panic!("It's not possible to reach the target starting from this robot configuration");
+        }
+
+        let mut width = self.initial_width;
+        loop {
+            if let Some(path) = self.solve_with_width(round, &start_positions, width) {
+                return path;
+            }
+
+            if width >= self.max_width {
+                panic!(
+                    "widening beam search exhausted its widening budget at max width {}",
+                    self.max_width
+                );
+            }
+            width = (width * 2).min(self.max_width);
+        }
+    }
+}
+
+/// Orders a beam search successor from high to low by its estimated total moves `f = g + h`, with
+/// ties broken in favor of the lower heuristic estimate `h`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct WideningBeamScore {
+    // Reordering these fields changes the derived `Ord` and `PartialOrd` implementations.
+    total: Reverse<usize>,
+    to_target: Reverse<usize>,
+}
+
+impl WideningBeamScore {
+    fn new(from_start: usize, to_target: usize) -> Self {
+        Self {
+            total: Reverse(from_start + to_target),
+            to_target: Reverse(to_target),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ricochet_board::{quadrant, Game, RobotPositions, Round, Symbol, Target};
+
+    use super::WideningBeamSearch;
+    use crate::{Path, Solver};
+
+    fn create_board() -> (RobotPositions, Game) {
+        let quadrants = quadrant::gen_quadrants()
+            .iter()
+            .step_by(3)
+            .cloned()
+            .enumerate()
+            .map(|(i, mut quad)| {
+                quad.rotate_to(quadrant::ORIENTATIONS[i]);
+                quad
+            })
+            .collect::<Vec<quadrant::BoardQuadrant>>();
+
+        let pos = RobotPositions::from_tuples(&[(0, 1), (5, 4), (7, 1), (7, 15)]);
+        (pos, Game::from_quadrants(&quadrants))
+    }
+
+    #[test]
+    fn on_target() {
+        let (_, game) = create_board();
+        let target = Target::Green(Symbol::Triangle);
+        let target_position = game.get_target_position(&target).unwrap();
+
+        let start = RobotPositions::from_tuples(&[(0, 1), (5, 4), target_position.into(), (7, 15)]);
+        let end = start.clone();
+
+        let round = Round::new(game.board().clone(), target, target_position);
+
+        let expected = Path::new(start.clone(), end, vec![]);
+        assert_eq!(
+            WideningBeamSearch::new(4, 64).solve(&round, start),
+            expected
+        );
+    }
+
+    #[test]
+    fn widens_past_a_too_narrow_starting_width() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let path = WideningBeamSearch::new(1, 256).solve(&round, pos);
+        assert!(round.target_reached(path.end_pos()));
+    }
+
+    #[test]
+    fn wide_enough_start_matches_optimal_length() {
+        let (pos, game) = create_board();
+        let target = Target::Yellow(Symbol::Hexagon);
+        let round = Round::new(
+            game.board().clone(),
+            target,
+            game.get_target_position(&target).unwrap(),
+        );
+
+        let optimal = crate::AStar::new().solve(&round, pos.clone());
+        let beam = WideningBeamSearch::new(256, 256).solve(&round, pos);
+
+        assert_eq!(beam.len(), optimal.len());
+    }
+}
@@ -0,0 +1,179 @@
+use rand::{Rng, SeedableRng};
+use ricochet_board::{Position, PositionEncoding, Robot, RobotPositions, ROBOTS};
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+
+/// Seed for the [`ZobristTable`](ZobristTable) backing
+/// [`ZobristVisitedNodes`](crate::util::ZobristVisitedNodes), so its hashes stay reproducible
+/// across runs instead of reseeding from entropy like [`ZobristTable::new`](ZobristTable::new)
+/// does.
+pub(crate) const ZOBRIST_SEED: u128 = 0x5a6f6272697374_2121;
+
+/// Precomputed random values used to incrementally hash a [`RobotPositions`](RobotPositions).
+///
+/// Holds one random `u64` per `(robot, cell)` pair. The hash of a position is the XOR of the four
+/// entries for its occupied cells, which makes it possible to derive the hash of a successor from
+/// the hash of its parent in O(1) instead of rehashing the whole position, see
+/// [`rehash_move`](ZobristTable::rehash_move).
+#[derive(Debug, Clone)]
+pub(crate) struct ZobristTable {
+    side_length: PositionEncoding,
+    // One vec of `side_length * side_length` random values per robot.
+    table: Vec<Vec<u64>>,
+}
+
+impl ZobristTable {
+    /// Creates a new table for a board with `side_length`, seeded once from entropy.
+    pub fn new(side_length: PositionEncoding) -> Self {
+        Self::build(rand_pcg::Pcg64Mcg::from_entropy(), side_length)
+    }
+
+    /// Creates a new table for a board with `side_length`, deterministically seeded from `seed`.
+    ///
+    /// Use this instead of [`new`](ZobristTable::new) when the resulting hashes need to stay the
+    /// same across runs, e.g. because they're persisted or compared between processes.
+    pub fn from_seed(seed: u128, side_length: PositionEncoding) -> Self {
+        Self::build(rand_pcg::Pcg64Mcg::new(seed), side_length)
+    }
+
+    fn build(mut rng: rand_pcg::Pcg64Mcg, side_length: PositionEncoding) -> Self {
+        let cell_count = side_length as usize * side_length as usize;
+        let table = ROBOTS
+            .iter()
+            .map(|_| (0..cell_count).map(|_| rng.gen()).collect())
+            .collect();
+
+        Self { side_length, table }
+    }
+
+    /// Computes the hash of `positions` from scratch by XORing the table entry of every robot's
+    /// cell.
+    pub fn hash(&self, positions: &RobotPositions) -> u64 {
+        ROBOTS.iter().fold(0, |hash, &robot| {
+            hash ^ self.table[Self::robot_index(robot)][self.cell_index(positions[robot])]
+        })
+    }
+
+    /// Derives the hash of the position reached by moving `robot` from `from` to `to`, given the
+    /// hash of the position it was moved from.
+    pub fn rehash_move(&self, hash: u64, robot: Robot, from: Position, to: Position) -> u64 {
+        let robot = Self::robot_index(robot);
+        hash ^ self.table[robot][self.cell_index(from)] ^ self.table[robot][self.cell_index(to)]
+    }
+
+    fn cell_index(&self, pos: Position) -> usize {
+        pos.column() as usize * self.side_length as usize + pos.row() as usize
+    }
+
+    fn robot_index(robot: Robot) -> usize {
+        match robot {
+            Robot::Red => 0,
+            Robot::Blue => 1,
+            Robot::Green => 2,
+            Robot::Yellow => 3,
+        }
+    }
+}
+
+/// A `RobotPositions` paired with its precomputed [`ZobristTable`](ZobristTable) hash.
+///
+/// Hashing a `PositionKey` only touches the cached `u64`, while equality still compares the full
+/// `RobotPositions` so a hash collision can never be mistaken for the same state.
+#[derive(Debug, Clone)]
+pub(crate) struct PositionKey {
+    positions: RobotPositions,
+    hash: u64,
+}
+
+impl PositionKey {
+    /// Creates a new key from `positions` and its precomputed `hash`.
+    pub fn new(positions: RobotPositions, hash: u64) -> Self {
+        Self { positions, hash }
+    }
+
+    /// Returns the wrapped positions.
+    pub fn positions(&self) -> &RobotPositions {
+        &self.positions
+    }
+
+    /// Returns the precomputed hash of the wrapped positions.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl PartialEq for PositionKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.positions == other.positions
+    }
+}
+
+impl Eq for PositionKey {}
+
+impl Hash for PositionKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+/// A `Hasher` that only ever hashes a single precomputed `u64` (written by
+/// [`PositionKey`](PositionKey)'s `Hash` impl) and returns it unchanged instead of mixing it
+/// further.
+#[derive(Default)]
+pub(crate) struct PassthroughHasher(u64);
+
+impl Hasher for PassthroughHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, _bytes: &[u8]) {
+        unreachable!("PositionKey only ever hashes through `write_u64`")
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.0 = i;
+    }
+}
+
+/// A `BuildHasher` producing [`PassthroughHasher`](PassthroughHasher)s, for use with maps and
+/// priority queues keyed by [`PositionKey`](PositionKey).
+pub(crate) type PassthroughBuildHasher = BuildHasherDefault<PassthroughHasher>;
+
+#[cfg(test)]
+mod tests {
+    use super::ZobristTable;
+    use ricochet_board::{Direction, Robot, RobotPositions};
+
+    #[test]
+    fn incremental_hash_matches_full_hash() {
+        let table = ZobristTable::new(16);
+        let start = RobotPositions::from_tuples(&[(0, 0), (5, 5), (10, 10), (15, 15)]);
+        let from = start[Robot::Red];
+
+        let moved = start.clone().move_in_direction(
+            &ricochet_board::Board::new_empty(16).wall_enclosure(),
+            Robot::Red,
+            Direction::Right,
+        );
+        let to = moved[Robot::Red];
+
+        let incremental = table.rehash_move(table.hash(&start), Robot::Red, from, to);
+        assert_eq!(incremental, table.hash(&moved));
+    }
+
+    #[test]
+    fn equal_positions_hash_equally() {
+        let table = ZobristTable::new(16);
+        let a = RobotPositions::from_tuples(&[(1, 2), (3, 4), (5, 6), (7, 8)]);
+        let b = a.clone();
+        assert_eq!(table.hash(&a), table.hash(&b));
+    }
+
+    #[test]
+    fn from_seed_is_deterministic() {
+        let positions = RobotPositions::from_tuples(&[(1, 2), (3, 4), (5, 6), (7, 8)]);
+        let a = ZobristTable::from_seed(super::ZOBRIST_SEED, 16);
+        let b = ZobristTable::from_seed(super::ZOBRIST_SEED, 16);
+        assert_eq!(a.hash(&positions), b.hash(&positions));
+    }
+}
@@ -5,7 +5,9 @@ use rand::seq::SliceRandom;
 use rand::{Rng, SeedableRng};
 use ricochet_board::generator::{Generator as BoardGenerator, CENTER_WALLS_FROM_SIDE_LENGTH};
 use ricochet_board::quadrant::DISTINCT_STANDARD_BOARDS;
-use ricochet_board::{quadrant, PositionEncoding, RobotPositions, Round};
+use ricochet_board::{
+    quadrant, Board, Game, Position, PositionEncoding, RobotPositions, Round, Target,
+};
 
 /// Seed used to generate boards.
 ///
@@ -14,7 +16,7 @@ use ricochet_board::{quadrant, PositionEncoding, RobotPositions, Round};
 const WALLS_SEED: u128 = 0xcafef00dd15ea5e5;
 
 /// Configuration to control the board generation.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum WallConfig {
     /// A fixed board is generated.
     Fix,
@@ -26,6 +28,13 @@ pub enum WallConfig {
     Variants(usize),
     /// A randomly generated board from a practically infinte set.
     Random,
+    /// A board generated by a momentum-biased random walk, laying short wall segments in runs
+    /// rather than scattering them evenly across quadrants.
+    ///
+    /// `momentum_prob` is the probability that a step reuses the walker's previous direction
+    /// instead of picking a new one uniformly; higher values produce longer, maze-like corridors.
+    /// `segments` is the number of steps the walker takes.
+    RandomWalk { momentum_prob: f64, segments: usize },
 }
 
 /// Configuration to control the selection of the target.
@@ -35,6 +44,12 @@ pub enum TargetConfig {
     FromList(Vec<(TargetColor, Coordinate)>),
     /// The target is chosen from the targets generated together with the board.
     Variants,
+    /// Like `Variants`, but the round is meant to be played as a tour of `usize` targets visited in
+    /// sequence rather than just one.
+    ///
+    /// Pair this with [`EnvironmentBuilder::new_round_sequence`] (passing the same count) to build
+    /// the tour, and `ricochet_solver::solve_sequence` to find the cheapest order to visit it in.
+    Sequence(usize),
 }
 
 /// Configuration to control the placement of the robots on the board.
@@ -47,7 +62,7 @@ pub enum RobotConfig {
 }
 
 /// Builder to create new rounds and positions from the environment configuration.
-#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+#[derive(Debug, Clone, PartialEq, Getters, CopyGetters)]
 pub struct EnvironmentBuilder {
     #[get_copy = "pub"]
     board_size: PositionEncoding,
@@ -78,9 +93,9 @@ impl EnvironmentBuilder {
         }
     }
 
-    /// Creates a new `Round`.
-    pub fn new_round(&mut self) -> Round {
-        let game = match self.walls {
+    /// Generates a new board according to `self.walls`.
+    fn generate_game(&mut self) -> Game {
+        match self.walls {
             WallConfig::Fix => {
                 BoardGenerator::from_seed(WALLS_SEED, self.board_size).generate_game()
             }
@@ -93,23 +108,77 @@ impl EnvironmentBuilder {
             )
             .generate_game(),
             WallConfig::Random => BoardGenerator::new(self.board_size).generate_game(),
-        };
+            WallConfig::RandomWalk {
+                momentum_prob,
+                segments,
+            } => BoardGenerator::from_seed(self.rng.gen(), self.board_size)
+                .generate_random_walk_game(momentum_prob, segments),
+        }
+    }
 
-        let (target, target_position) = match &self.targets {
+    /// Picks every target available under `self.targets`, in a random order.
+    fn shuffled_targets(&mut self, game: &Game) -> Vec<(Target, Position)> {
+        let mut targets: Vec<(Target, Position)> = match &self.targets {
             TargetConfig::FromList(targets) => {
-                let (t, tp) = *targets.choose(&mut self.rng).expect("target list is empty");
-                (t.into(), tp.into())
+                targets.iter().map(|&(t, tp)| (t.into(), tp.into())).collect()
+            }
+            TargetConfig::Variants | TargetConfig::Sequence(_) => {
+                game.targets().iter().map(|(&t, &tp)| (t, tp)).collect()
             }
-            TargetConfig::Variants => game
-                .targets()
-                .iter()
-                .collect::<Vec<_>>()
-                .choose(&mut self.rng)
-                .map(|&(&t, &tp)| (t, tp))
-                .expect("could not get a target from a `Game`"),
         };
+        targets.shuffle(&mut self.rng);
+        targets
+    }
+
+    /// Creates a new `Round`.
+    ///
+    /// If every available target is unreachable on the generated board (the walls trap it, which
+    /// `WallConfig::RandomWalk` corridors can do by accident), a fresh board is generated and
+    /// retried.
+    pub fn new_round(&mut self) -> Round {
+        loop {
+            let game = self.generate_game();
+            let targets = self.shuffled_targets(&game);
+            assert!(!targets.is_empty(), "no target available to build a round from");
+
+            if let Some(&(target, target_position)) = targets
+                .iter()
+                .find(|&&(_, pos)| !Self::target_is_trapped(game.board(), pos))
+            {
+                return Round::new(game.board().clone(), target, target_position);
+            }
+        }
+    }
+
+    /// Checks whether `target_position` is unreachable by any robot slide, using
+    /// [`Board::move_lower_bounds`]'s optimistic-blocker lower bound: a cell only stays at
+    /// `u8::MAX` there if no slide from any direction could ever stop on it, no matter how the
+    /// other robots are placed as blockers.
+    fn target_is_trapped(board: &Board, target_position: Position) -> bool {
+        board
+            .move_lower_bounds(target_position)
+            .iter()
+            .filter(|&&bound| bound != u8::MAX)
+            .count()
+            <= 1
+    }
+
+    /// Creates a sequence of `target_count` rounds on the same board, to be played one after the
+    /// other.
+    ///
+    /// The targets are drawn without replacement from the available targets in one shuffled order,
+    /// cycling back to the start of that same order once they're exhausted.
+    pub fn new_round_sequence(&mut self, target_count: usize) -> Vec<Round> {
+        let game = self.generate_game();
+        let targets = self.shuffled_targets(&game);
+        assert!(!targets.is_empty(), "no target available to build a round from");
 
-        Round::new(game.board().clone(), target, target_position)
+        (0..target_count)
+            .map(|i| {
+                let (target, target_position) = targets[i % targets.len()];
+                Round::new(game.board().clone(), target, target_position)
+            })
+            .collect()
     }
 
     /// Creates a new `RobotPositions`.
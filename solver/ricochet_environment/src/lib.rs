@@ -8,6 +8,7 @@ use pyo3::prelude::*;
 use ricochet_board::{
     Board, Direction, PositionEncoding, Robot, RobotPositions, Round, Symbol, Target,
 };
+use ricochet_solver::util::LeastMovesBoard;
 
 /// The base module of the created package.
 #[pymodule]
@@ -31,12 +32,14 @@ pub type Coordinate = (PositionEncoding, PositionEncoding);
 /// - the positions of the robots in the order red, blue, green, yellow as (column, row) tuples
 /// - the position of the target
 /// - the color of the target
+/// - the number of targets still to be reached after the current one
 pub type Observation<'a> = (
     &'a PyArray2<bool>,
     &'a PyArray2<bool>,
     Vec<Coordinate>,
     Coordinate,
     usize,
+    usize,
 );
 
 /// An action that can be performed in the environment.
@@ -64,26 +67,87 @@ pub enum TargetColor {
 #[derive(Debug, Clone)]
 pub struct RustyEnvironment {
     config: EnvironmentBuilder,
-    round: Round,
+    /// The sequence of targets to reach this episode, one round per target, all sharing the same
+    /// board. Regenerated on every [`reset`](RustyEnvironment::reset).
+    rounds: Vec<Round>,
+    /// Index into `rounds` of the target currently being pursued.
+    current_target_index: usize,
     wall_observation: (Array2<bool>, Array2<bool>),
     starting_position: RobotPositions,
     current_position: RobotPositions,
     steps_taken: usize,
+    reward_shaping: Option<RewardShaping>,
+}
+
+/// Potential-based dense reward shaping, see [Ng, Harada, and Russel 1999](https://people.eecs.berkeley.edu/~russell/papers/icml99-shaping.pdf).
+///
+/// Adds `gamma * potential(next_state) - potential(state)` to the base reward every step. Since
+/// this term telescopes to `potential(goal) - potential(start)` over any full episode, it's
+/// policy-invariant: it only makes the reward dense, it never changes the optimal policy.
+#[derive(Debug, Clone)]
+struct RewardShaping {
+    /// Discount applied to the potential of the state reached by a step.
+    gamma: f64,
+    /// Used as the potential function `Φ(s) = -min_moves(s)`: the closer a state is to the
+    /// target, the higher its potential.
+    potential_board: LeastMovesBoard,
+}
+
+impl RewardShaping {
+    /// Builds the potential board for the current target of `round`.
+    fn new(round: &Round, gamma: f64) -> Self {
+        Self {
+            gamma,
+            potential_board: LeastMovesBoard::new(round.board(), round.target_position()),
+        }
+    }
+
+    /// Rebuilds the potential board, to be called whenever `round`'s walls or target change.
+    fn rebuild(&mut self, round: &Round) {
+        self.potential_board = LeastMovesBoard::new(round.board(), round.target_position());
+    }
+
+    /// Returns `Φ(pos) = -min_moves(pos)`, the potential of `pos` towards `round`'s target.
+    fn potential(&self, round: &Round, pos: &RobotPositions) -> f64 {
+        -(self.potential_board.min_moves(pos, round.target()) as f64)
+    }
+
+    /// Returns the shaping term `gamma * Φ(next) - Φ(previous)` to add to the base reward.
+    fn shaping_term(&self, round: &Round, previous: &RobotPositions, next: &RobotPositions) -> f64 {
+        self.gamma * self.potential(round, next) - self.potential(round, previous)
+    }
 }
 
 #[pymethods]
 impl RustyEnvironment {
     /// Creates a new environment with the given configuration.
     ///
-    /// For more information on possible configurations see the config enums docs.
+    /// For more information on possible configurations see the config enums docs. `target_count`
+    /// targets are chained into one episode: reaching one advances to the next from the current
+    /// robot positions instead of ending the episode, until the last target is reached. Passing
+    /// `1` keeps the single-target behavior. If `shaping` is `true`, every step's reward
+    /// additionally includes a potential-based dense shaping term discounted by `gamma`, see
+    /// [`RewardShaping`](RewardShaping).
     #[new]
     pub fn new(
         board_size: PositionEncoding,
         walls: WallConfig,
         targets: TargetConfig,
         robots: RobotConfig,
+        target_count: usize,
+        shaping: bool,
+        gamma: f64,
     ) -> Self {
-        Self::new_seeded(board_size, walls, targets, robots, rand::random())
+        Self::new_seeded(
+            board_size,
+            walls,
+            targets,
+            robots,
+            target_count,
+            shaping,
+            gamma,
+            rand::random(),
+        )
     }
 
     /// Creates a new environment with the given configuration and seed to make it reproducible.
@@ -93,24 +157,34 @@ impl RustyEnvironment {
         walls: WallConfig,
         targets: TargetConfig,
         robots: RobotConfig,
+        target_count: usize,
+        shaping: bool,
+        gamma: f64,
         seed: u128,
     ) -> Self {
         let mut config = EnvironmentBuilder::new_seeded(board_size, walls, targets, robots, seed);
-        let round = config.new_round();
+        let rounds = config.new_round_sequence(target_count);
         let starting_position = loop {
             let pos = config.new_positions();
-            if !round.target_reached(&pos) {
+            if !rounds[0].target_reached(&pos) {
                 break pos;
             }
         };
+        let reward_shaping = if shaping {
+            Some(RewardShaping::new(&rounds[0], gamma))
+        } else {
+            None
+        };
 
         Self {
-            wall_observation: create_wall_bitboards(round.board()),
-            round,
+            wall_observation: create_wall_bitboards(rounds[0].board()),
+            rounds,
+            current_target_index: 0,
             current_position: starting_position.clone(),
             starting_position,
             steps_taken: 0,
             config,
+            reward_shaping,
         }
     }
 
@@ -122,17 +196,33 @@ impl RustyEnvironment {
 
     /// Performs an action to change the environment and returns a tuple (observation, reward, done).
     pub fn step(&mut self, py_gil: Python, action: Action) -> PyObject {
+        let previous_position = self.current_position.clone();
         self.current_position = self.current_position.clone().move_in_direction(
-            self.round.board(),
+            self.round().board(),
             action.robot,
             action.direction,
         );
 
         let mut reward = 0.0;
-        let mut done = false;
-        if self.round.target_reached(&self.current_position) {
+        let target_reached = self.round().target_reached(&self.current_position);
+        if target_reached {
             reward = 1.0;
-            done = true;
+        }
+
+        if let Some(shaping) = &self.reward_shaping {
+            reward += shaping.shaping_term(self.round(), &previous_position, &self.current_position);
+        }
+
+        let mut done = false;
+        if target_reached {
+            if self.current_target_index + 1 < self.rounds.len() {
+                self.current_target_index += 1;
+                if let Some(shaping) = &mut self.reward_shaping {
+                    shaping.rebuild(self.round());
+                }
+            } else {
+                done = true;
+            }
         }
 
         let output = (self.observation(py_gil), reward, done);
@@ -141,25 +231,30 @@ impl RustyEnvironment {
 
     /// Resets the environment which means a new state is created according to the configuration.
     pub fn reset(&mut self, py_gil: Python) -> PyObject {
-        self.round = self.config.new_round();
+        self.rounds = self.config.new_round_sequence(self.rounds.len());
+        self.current_target_index = 0;
         if *self.config.walls() != WallConfig::Fix {
-            self.wall_observation = create_wall_bitboards(self.round.board());
+            self.wall_observation = create_wall_bitboards(self.round().board());
         }
         self.starting_position = loop {
             let pos = self.config.new_positions();
-            if !self.round.target_reached(&pos) {
+            if !self.round().target_reached(&pos) {
                 break pos;
             }
         };
         self.current_position = self.starting_position.clone();
         self.steps_taken = 0;
 
+        if let Some(shaping) = &mut self.reward_shaping {
+            shaping.rebuild(self.round());
+        }
+
         self.get_state(py_gil)
     }
 
     /// Returns a simple drawing of the walls with unicode box drawing characters.
     pub fn render(&self) -> String {
-        ricochet_board::draw_board(self.round.board().get_walls())
+        ricochet_board::draw_board(self.round().board().get_walls())
     }
 
     /// Get the current state of the environment.
@@ -169,22 +264,29 @@ impl RustyEnvironment {
 }
 
 impl RustyEnvironment {
+    /// Returns the round for the target currently being pursued.
+    fn round(&self) -> &Round {
+        &self.rounds[self.current_target_index]
+    }
+
     /// Creates an observation from the current state of the environment.
     fn observation<'a>(&self, py_gil: Python<'a>) -> Observation<'a> {
-        let target_pos = self.round.target_position();
-        let target = match self.round.target() {
+        let target_pos = self.round().target_position();
+        let target = match self.round().target() {
             Target::Red(_) => 0,
             Target::Blue(_) => 1,
             Target::Green(_) => 2,
             Target::Yellow(_) => 3,
             Target::Spiral => 4,
         };
+        let remaining_targets = self.rounds.len() - self.current_target_index - 1;
         (
             self.wall_observation.0.view().to_pyarray(py_gil),
             self.wall_observation.1.view().to_pyarray(py_gil),
             robot_positions_as_vec(&self.current_position),
             (target_pos.column(), target_pos.row()),
             target,
+            remaining_targets,
         )
     }
 }